@@ -4,6 +4,10 @@ pub enum Auth {
     None,
     QueryPassword(User),
     QueryToken(String),
+    /// Logs in once using the credentials and authenticates using the session cookie afterwards
+    /// The password is never sent as part of a URL
+    /// If the session expires, the client logs in again transparently
+    Session(User),
 }
 
 #[derive(Serialize)]
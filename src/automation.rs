@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Automation {
+    pub id: u64,
+    pub owner: String,
+    pub data: AutomationData,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationData {
+    pub name: String,
+    pub description: String,
+    /// The Homescript which is executed when the automation is triggered
+    pub homescript_id: String,
+    pub enabled: bool,
+    pub trigger: AutomationTrigger,
+}
+
+/// Specifies when an automation is executed
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AutomationTrigger {
+    /// Runs at the given time on the given days of the week (`0` is Sunday)
+    Cron { hour: u8, minute: u8, days: Vec<u8> },
+    /// Runs at sunrise, the offset may be negative to run before the sun rises
+    Sunrise { offset_minutes: i32, days: Vec<u8> },
+    /// Runs at sunset, the offset may be negative to run before the sun sets
+    Sunset { offset_minutes: i32, days: Vec<u8> },
+    /// Runs when the owner logs in
+    OnLogin,
+    /// Runs when the owner logs out
+    OnLogout,
+    /// Runs when the owner receives a notification
+    OnNotification,
+}
+
+#[derive(Serialize)]
+struct ModifyAutomationRequest<'request> {
+    id: u64,
+    data: &'request AutomationData,
+}
+
+#[derive(Serialize)]
+struct DeleteAutomationRequest {
+    id: u64,
+}
+
+#[derive(Serialize)]
+struct AutomationStateRequest {
+    enabled: bool,
+}
+
+impl Client {
+    /// Returns the personal automations of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let automations = client.list_automations().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_automations(&self) -> Result<Vec<Automation>> {
+        self.call(
+            Endpoint::get("/api/automation/list/personal")
+                .json::<Vec<Automation>>()
+                .permission(Permission::Automation),
+        )
+        .await
+    }
+
+    /// Creates a new automation which runs an existing Homescript
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, AutomationData, AutomationTrigger};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.create_automation(&AutomationData {
+    ///         name: "Morning lights".to_string(),
+    ///         description: "Turns on the lights on weekdays".to_string(),
+    ///         homescript_id: "lights_on".to_string(),
+    ///         enabled: true,
+    ///         trigger: AutomationTrigger::Sunrise {
+    ///             offset_minutes: -30,
+    ///             days: vec![1, 2, 3, 4, 5],
+    ///         },
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_automation(&self, data: &AutomationData) -> Result<()> {
+        self.call(Endpoint::post("/api/automation/add", data).permission(Permission::Automation))
+            .await
+    }
+
+    /// Replaces the data of an existing automation
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let mut automation = client.list_automations().await.unwrap().remove(0);
+    ///     automation.data.enabled = false;
+    ///     client.modify_automation(automation.id, &automation.data).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_automation(&self, id: u64, data: &AutomationData) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/automation/modify",
+                ModifyAutomationRequest { id, data },
+            )
+            .permission(Permission::Automation),
+        )
+        .await
+    }
+
+    /// Deletes an automation, the Homescript it runs is not affected
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_automation(42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_automation(&self, id: u64) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/automation/delete", DeleteAutomationRequest { id })
+                .permission(Permission::Automation),
+        )
+        .await
+    }
+
+    /// Enables or disables the execution of all automations on the target system
+    /// The `enabled` flag of each individual automation is preserved
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     // Pause all automations during the vacation
+    ///     client.set_automations_enabled(false).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_automations_enabled(&self, enabled: bool) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/automation/state/global",
+                AutomationStateRequest { enabled },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Asserts that the trigger is serialized to the given JSON and parsed back unchanged
+    fn assert_wire_format(trigger: AutomationTrigger, expected: Value) {
+        assert_eq!(serde_json::to_value(&trigger).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<AutomationTrigger>(expected).unwrap(),
+            trigger
+        );
+    }
+
+    #[test]
+    fn cron() {
+        assert_wire_format(
+            AutomationTrigger::Cron {
+                hour: 7,
+                minute: 30,
+                days: vec![1, 2],
+            },
+            json!({ "kind": "cron", "hour": 7, "minute": 30, "days": [1, 2] }),
+        );
+    }
+
+    #[test]
+    fn sun_offsets() {
+        assert_wire_format(
+            AutomationTrigger::Sunrise {
+                offset_minutes: -30,
+                days: vec![1],
+            },
+            json!({ "kind": "sunrise", "offsetMinutes": -30, "days": [1] }),
+        );
+        assert_wire_format(
+            AutomationTrigger::Sunset {
+                offset_minutes: 15,
+                days: vec![0, 6],
+            },
+            json!({ "kind": "sunset", "offsetMinutes": 15, "days": [0, 6] }),
+        );
+    }
+
+    #[test]
+    fn events() {
+        assert_wire_format(AutomationTrigger::OnLogin, json!({ "kind": "onLogin" }));
+        assert_wire_format(AutomationTrigger::OnLogout, json!({ "kind": "onLogout" }));
+        assert_wire_format(
+            AutomationTrigger::OnNotification,
+            json!({ "kind": "onNotification" }),
+        );
+    }
+}
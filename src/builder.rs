@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Certificate, Proxy,
+};
+
+use crate::{
+    cassette::Cassette, errors::Result, Auth, CassetteMode, Client, RetryPolicy, HTTP_USER_AGENT,
+};
+
+/// Configures and creates a `Client`
+/// Every request issued by the resulting client uses this configuration
+/// ```rust no_run
+/// use std::time::Duration;
+/// use smarthome_sdk_rs::{Auth, Client};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::builder("https://smarthome.box")
+///         .auth(Auth::QueryToken("token".to_string()))
+///         .connect_timeout(Duration::from_secs(5))
+///         .timeout(Duration::from_secs(30))
+///         .user_agent_suffix("my-tool/1.0")
+///         .build()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub struct ClientBuilder {
+    raw_url: String,
+    auth: Auth,
+    version_check: bool,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    root_certificates: Vec<Certificate>,
+    built_in_root_certificates: bool,
+    proxy: Option<Proxy>,
+    default_headers: HeaderMap,
+    user_agent_suffix: Option<String>,
+    http_client: Option<reqwest::Client>,
+    cassette: Option<(PathBuf, CassetteMode)>,
+    retry_policy: RetryPolicy,
+}
+
+impl ClientBuilder {
+    /// Creates a builder targeting the given Smarthome URL
+    /// By default, no authentication is used, the server version is validated and
+    /// temporary failures are retried according to `RetryPolicy::default`
+    pub fn new(raw_url: &str) -> Self {
+        Self {
+            raw_url: raw_url.to_string(),
+            auth: Auth::None,
+            version_check: true,
+            connect_timeout: None,
+            timeout: None,
+            root_certificates: vec![],
+            built_in_root_certificates: true,
+            proxy: None,
+            default_headers: HeaderMap::new(),
+            user_agent_suffix: None,
+            http_client: None,
+            cassette: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the authentication mode of the client
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Whether the server's version is validated against `SERVER_VERSION_REQUIREMENT`
+    pub fn version_check(mut self, enabled: bool) -> Self {
+        self.version_check = enabled;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for an entire request, from connecting until the response body has been read
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Trusts an additional root certificate, for instance the CA of a self-signed reverse proxy
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Only trusts the given certificate, the built-in root certificates are disabled
+    pub fn pin_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates = vec![certificate];
+        self.built_in_root_certificates = false;
+        self
+    }
+
+    /// Routes every request through the given proxy
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Adds a header which is sent with every request
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Adds headers which are sent with every request
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Appends a suffix to the SDK's user agent, for example `my-tool/1.0`
+    pub fn user_agent_suffix(mut self, suffix: &str) -> Self {
+        self.user_agent_suffix = Some(suffix.to_string());
+        self
+    }
+
+    /// Uses a pre-built `reqwest` client
+    /// Timeouts, certificates, proxies, headers and the user agent of this builder are ignored in this case
+    /// It is also used for streaming Homescript runs, which require a connection using HTTP/1.1
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Controls which requests are repeated after failing temporarily, `RetryPolicy::default` is used otherwise
+    /// Use `RetryPolicy::disabled` in order to send every request only once
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Records every request and its response into the given file, which is replaced if it exists
+    /// Credentials of the authentication mode are scrubbed from URLs and bodies
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Auth, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::builder("https://staging.smarthome.box")
+    ///         .auth(Auth::QueryToken("token".to_string()))
+    ///         .record_cassette("tests/cassettes/lamp.jsonl")
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     client.set_power("desk_lamp", true).await.unwrap();
+    /// }
+    /// ```
+    pub fn record_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some((path.into(), CassetteMode::Record));
+        self
+    }
+
+    /// Answers every request using a file created by `record_cassette`, the server is never contacted
+    /// Requests without a matching recording fail using `Error::Cassette`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Auth, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::builder("https://staging.smarthome.box")
+    ///         .auth(Auth::QueryToken("token".to_string()))
+    ///         .replay_cassette("tests/cassettes/lamp.jsonl")
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     client.set_power("desk_lamp", true).await.unwrap();
+    /// }
+    /// ```
+    pub fn replay_cassette(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cassette = Some((path.into(), CassetteMode::Replay));
+        self
+    }
+
+    /// Creates the client, validates the server's compatibility and logs in
+    pub async fn build(self) -> Result<Client> {
+        let (client, upgrade_client) = match &self.http_client {
+            Some(client) => (client.clone(), client.clone()),
+            None => (
+                self.http_builder().build()?,
+                // Servers behind a TLS proxy might negotiate HTTP/2, which does not support WebSocket upgrades
+                self.http_builder().http1_only().build()?,
+            ),
+        };
+
+        let cassette = match &self.cassette {
+            Some((path, mode)) => Some(Cassette::new(path, *mode, &self.auth).await?),
+            None => None,
+        };
+
+        Client::connect(
+            client,
+            upgrade_client,
+            &self.raw_url,
+            self.auth,
+            self.version_check,
+            cassette,
+            self.retry_policy,
+        )
+        .await
+    }
+
+    /// Applies the configuration of this builder to a new `reqwest` builder
+    fn http_builder(&self) -> reqwest::ClientBuilder {
+        let user_agent = match &self.user_agent_suffix {
+            Some(suffix) => format!("{HTTP_USER_AGENT} {suffix}"),
+            None => HTTP_USER_AGENT.to_string(),
+        };
+
+        let mut builder = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .default_headers(self.default_headers.clone())
+            .tls_built_in_root_certs(self.built_in_root_certificates);
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        builder
+    }
+}
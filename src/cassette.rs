@@ -0,0 +1,302 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+use reqwest::header::{HeaderValue, CONTENT_TYPE};
+use reqwest::{Method, Request, Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+
+use crate::errors::{Error, Result};
+use crate::Auth;
+
+/// Replaces credentials in recorded request and response bodies
+const SCRUBBED: &str = "[scrubbed]";
+
+/// Selects whether a client records its traffic into a cassette file or replays it from one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Every request is sent to the server and the exchange is appended to the cassette
+    Record,
+    /// Requests are answered using the cassette, the server is never contacted
+    Replay,
+}
+
+/// Describes why a cassette could not be used
+#[derive(Debug)]
+pub enum CassetteError {
+    /// The cassette file could not be read or written
+    Io(std::io::Error),
+    /// The cassette file does not contain valid interactions
+    Format(serde_json::Error),
+    /// No recorded interaction matches the request
+    Unmatched { method: Method, url: String },
+}
+
+impl Display for CassetteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CassetteError::Io(err) => write!(f, "could not access cassette file: {err}"),
+            CassetteError::Format(err) => write!(f, "invalid cassette file: {err}"),
+            CassetteError::Unmatched { method, url } => {
+                write!(f, "no recorded interaction matches `{method} {url}`")
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct RecordedRequest {
+    method: String,
+    /// The path and query of the request, without the server's address
+    url: String,
+    body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RecordedResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: RecordedBody,
+}
+
+/// Text bodies are stored as-is so that cassettes remain readable and editable
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+enum RecordedBody {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Records or replays the HTTP traffic of a client
+/// Cassette files contain one JSON encoded interaction per line, so recording only appends
+pub(crate) struct Cassette {
+    mode: CassetteMode,
+    /// The file which recorded interactions are appended to, `None` when replaying
+    file: Option<tokio::sync::Mutex<File>>,
+    /// Query parameters which are added by `build_request` for authentication
+    query_credentials: Vec<(String, String)>,
+    /// Values which must never be written to the cassette
+    secrets: Vec<String>,
+    /// The interactions which have not been replayed yet, always empty when recording
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    /// Creates a cassette for the given file
+    /// In record mode the file is truncated, in replay mode it is loaded immediately
+    pub(crate) async fn new(path: &Path, mode: CassetteMode, auth: &Auth) -> Result<Self> {
+        let (file, interactions) = match mode {
+            CassetteMode::Record => {
+                let file = File::create(path).await.map_err(CassetteError::Io)?;
+                (Some(tokio::sync::Mutex::new(file)), vec![])
+            }
+            CassetteMode::Replay => {
+                let raw = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(CassetteError::Io)?;
+                let interactions = raw
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(serde_json::from_str::<Interaction>)
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(CassetteError::Format)?;
+                if let Some(interaction) = interactions
+                    .iter()
+                    .find(|interaction| StatusCode::from_u16(interaction.response.status).is_err())
+                {
+                    return Err(CassetteError::Format(serde::de::Error::custom(format!(
+                        "invalid status code {}",
+                        interaction.response.status
+                    )))
+                    .into());
+                }
+                (None, interactions)
+            }
+        };
+
+        let (query_credentials, secrets) = match auth {
+            Auth::None => (vec![], vec![]),
+            Auth::QueryPassword(user) => (
+                vec![
+                    ("username".to_string(), user.username.clone()),
+                    ("password".to_string(), user.password.clone()),
+                ],
+                vec![user.password.clone()],
+            ),
+            Auth::QueryToken(token) => (
+                vec![("token".to_string(), token.clone())],
+                vec![token.clone()],
+            ),
+            Auth::Session(user) => (vec![], vec![user.password.clone()]),
+        };
+
+        Ok(Self {
+            mode,
+            file,
+            query_credentials,
+            secrets,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// Sends the request or answers it from the cassette, depending on the mode
+    async fn send(&self, client: &reqwest::Client, request: Request) -> Result<Response> {
+        let recorded = self.recorded_request(&request);
+        match self.mode {
+            CassetteMode::Record => {
+                let url = request.url().clone();
+                let response = client.execute(request).await?;
+                // Protocol upgrades cannot be replayed, the connection is handed to the caller
+                if response.status() == StatusCode::SWITCHING_PROTOCOLS {
+                    return Ok(response);
+                }
+
+                let status = response.status();
+                let headers = response.headers().clone();
+                let body = response.bytes().await?;
+
+                self.record(Interaction {
+                    request: recorded,
+                    response: RecordedResponse {
+                        status: status.as_u16(),
+                        content_type: headers
+                            .get(CONTENT_TYPE)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string),
+                        body: match std::str::from_utf8(&body) {
+                            Ok(text) => RecordedBody::Text(self.scrub(text)),
+                            Err(_) => RecordedBody::Binary(body.to_vec()),
+                        },
+                    },
+                })
+                .await?;
+
+                let mut builder = http::Response::builder().status(status).url(url);
+                if let Some(response_headers) = builder.headers_mut() {
+                    *response_headers = headers;
+                }
+                Ok(Response::from(
+                    builder.body(body).expect("recorded response is valid"),
+                ))
+            }
+            CassetteMode::Replay => {
+                let interaction = {
+                    let mut interactions = self
+                        .interactions
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner);
+                    let index = interactions
+                        .iter()
+                        .position(|interaction| interaction.request == recorded)
+                        .ok_or_else(|| CassetteError::Unmatched {
+                            method: request.method().clone(),
+                            url: recorded.url.clone(),
+                        })?;
+                    interactions.remove(index)
+                };
+
+                let response = interaction.response;
+                let mut builder = http::Response::builder()
+                    .status(response.status)
+                    .url(request.url().clone());
+                if let Some(content_type) = response
+                    .content_type
+                    .and_then(|value| HeaderValue::from_str(&value).ok())
+                {
+                    builder = builder.header(CONTENT_TYPE, content_type);
+                }
+                let body = match response.body {
+                    RecordedBody::Text(text) => text.into_bytes(),
+                    RecordedBody::Binary(bytes) => bytes,
+                };
+                Ok(Response::from(builder.body(body).expect(
+                    "status codes are validated when loading the cassette",
+                )))
+            }
+        }
+    }
+
+    /// Appends the interaction to the cassette file
+    async fn record(&self, interaction: Interaction) -> Result<()> {
+        let mut line = serde_json::to_vec(&interaction)?;
+        line.push(b'\n');
+
+        let mut file = self
+            .file
+            .as_ref()
+            .expect("cassettes in record mode own a file")
+            .lock()
+            .await;
+        file.write_all(&line).await.map_err(CassetteError::Io)?;
+        file.flush().await.map_err(CassetteError::Io)?;
+        Ok(())
+    }
+
+    /// Describes the request without the server's address and credentials
+    fn recorded_request(&self, request: &Request) -> RecordedRequest {
+        RecordedRequest {
+            method: request.method().to_string(),
+            url: self.scrub_url(request.url()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| self.scrub(&String::from_utf8_lossy(body))),
+        }
+    }
+
+    fn scrub_url(&self, url: &Url) -> String {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .into_owned()
+            .filter(|pair| !self.query_credentials.contains(pair))
+            .collect();
+        match pairs.is_empty() {
+            true => url.path().to_string(),
+            false => format!(
+                "{}?{}",
+                url.path(),
+                url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(pairs)
+                    .finish()
+            ),
+        }
+    }
+
+    /// Replaces credentials which are encoded as JSON strings
+    fn scrub(&self, text: &str) -> String {
+        self.secrets
+            .iter()
+            .filter(|secret| !secret.is_empty())
+            .filter_map(|secret| serde_json::to_string(secret).ok())
+            .fold(text.to_string(), |text, secret| {
+                text.replace(&secret, &format!("\"{SCRUBBED}\""))
+            })
+    }
+}
+
+/// Sends a request directly or through the given cassette
+pub(crate) async fn send(
+    client: &reqwest::Client,
+    cassette: Option<&Cassette>,
+    request: Request,
+) -> Result<Response> {
+    match cassette {
+        Some(cassette) => cassette.send(client, request).await,
+        None => Ok(client.execute(request).await?),
+    }
+}
+
+impl From<CassetteError> for Error {
+    fn from(err: CassetteError) -> Self {
+        Self::Cassette(err)
+    }
+}
@@ -1,34 +1,47 @@
-use reqwest::{Method, StatusCode, Url};
+use std::sync::Arc;
+
+use reqwest::{cookie::CookieStore, cookie::Jar, header::SET_COOKIE, Method, StatusCode, Url};
 use serde::Deserialize;
 
 use crate::{
     auth::Token,
+    cassette::Cassette,
     errors::{Error, Result},
-    version,
+    request, version,
     version::VersionResponse,
-    Auth, HTTP_USER_AGENT,
+    Auth, ClientBuilder, RetryPolicy,
 };
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TokenResponse {
     username: String,
-    _token_label: String,
+    token_label: String,
 }
 
 /// The client represents an object-oriented approach to interact with the server
 /// Any operation is implemented as a function on this struct
-/// It is created using the associative new function
+/// It is created using the associative new function or `Client::builder`
 /// ```rust
 /// // The SDK requires an aync runtime (Async excluded from this example)
 /// use smarthome_sdk_rs::{Client, Auth};
 /// ```
 pub struct Client {
     pub client: reqwest::Client,
+    /// Only speaks HTTP/1.1 and is used for WebSocket upgrades, which are impossible over HTTP/2
+    pub(crate) upgrade_client: reqwest::Client,
     pub auth: Auth,
     pub smarthome_url: Url,
     pub smarthome_version: VersionResponse,
     pub username: Option<String>,
+    /// The label of the token which is used for authentication, only set when using `Auth::QueryToken`
+    pub token_label: Option<String>,
+    /// Holds the session cookie when using `Auth::Session`
+    pub(crate) cookie_jar: Arc<Jar>,
+    /// Records or replays every request when configured using `ClientBuilder::record_cassette` or `ClientBuilder::replay_cassette`
+    pub(crate) cassette: Option<Cassette>,
+    /// Decides which failed requests are repeated, see `ClientBuilder::retry_policy`
+    pub(crate) retry_policy: RetryPolicy,
 }
 
 impl Client {
@@ -46,20 +59,57 @@ impl Client {
     /// }
     /// ```
     pub async fn new(raw_url: &str, auth: Auth, do_version_check: bool) -> Result<Self> {
+        ClientBuilder::new(raw_url)
+            .auth(auth)
+            .version_check(do_version_check)
+            .build()
+            .await
+    }
+
+    /// Returns a builder which allows further configuration of the client
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::builder("https://smarthome.box")
+    ///         .auth(Auth::None)
+    ///         .build()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn builder(raw_url: &str) -> ClientBuilder {
+        ClientBuilder::new(raw_url)
+    }
+
+    /// Creates a new client using the given `reqwest` clients for every request
+    pub(crate) async fn connect(
+        client: reqwest::Client,
+        upgrade_client: reqwest::Client,
+        raw_url: &str,
+        auth: Auth,
+        do_version_check: bool,
+        cassette: Option<Cassette>,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self> {
         // Parse the source url
         let smarthome_url = Url::parse(raw_url)?;
-        // Default reqwest client with a pre-set user agent is created
-        let client = reqwest::Client::builder()
-            .user_agent(HTTP_USER_AGENT)
-            .build()?;
         // Fetches the current version from the Smarthome server
         let mut version_url = smarthome_url.clone();
         version_url.set_path("/api/version");
-        let res = reqwest::get(version_url).await?;
+        let res = request::send_with_retries(
+            &client,
+            cassette.as_ref(),
+            &retry_policy,
+            client.get(version_url).build()?,
+            false,
+        )
+        .await?;
         // Handle errors which could occur during fetching
         let version = match res.status() {
             StatusCode::OK => res.json::<VersionResponse>().await?,
-            code => return Err(Error::Smarthome(code)),
+            _ => return Err(Error::from_response(Method::GET, res).await),
         };
 
         // Check if the SDK's version constraint is fulfilled by the server
@@ -69,10 +119,15 @@ impl Client {
         ) {
             (true, Ok(true)) | (false, Ok(_)) => Self {
                 client,
+                upgrade_client,
                 auth,
                 smarthome_url,
                 smarthome_version: version,
                 username: None,
+                token_label: None,
+                cookie_jar: Arc::new(Jar::default()),
+                cassette,
+                retry_policy,
             },
             (true, Ok(false)) => return Err(Error::IncompatibleVersion(version.smarthome_version)),
             (_, Err(err)) => return Err(err),
@@ -82,41 +137,62 @@ impl Client {
         // Also obtains the client's username in case token authentication is used
         match &client.auth {
             Auth::None => Ok(client),
-            auth => {
-                client.username = Some(login_with_credentials(&client.smarthome_url, auth).await?);
+            _ => {
+                let (username, token_label) = client.login_with_credentials().await?;
+                client.username = Some(username);
+                client.token_label = token_label;
                 Ok(client)
             }
         }
     }
-}
 
-/// Validates the client's credentials and returns a username
-async fn login_with_credentials(base_url: &Url, auth: &Auth) -> Result<String> {
-    let mut login_url = base_url.clone();
-    // Choose an adequate URL depending on the authentication mode
-    login_url.set_path(match auth {
-        Auth::QueryToken(_) => "/api/login/token",
-        Auth::QueryPassword(_) => "/api/login",
-        _ => unreachable!("login may not be called when using auth method `None`"),
-    });
-    // Perform the request
-    let req = reqwest::Client::new().request(Method::POST, login_url);
-    let res = match auth {
-        Auth::QueryPassword(user) => req.json(&user),
-        Auth::QueryToken(token) => req.json(&Token {
-            token: token.to_string(),
-        }),
-        _ => unreachable!("login may not be called when using auth method `None`"),
-    }
-    .send()
-    .await?;
-    // Handle smarthome-errors which could occur during login
-    match res.status() {
-        StatusCode::OK | StatusCode::NO_CONTENT => match auth {
-            Auth::QueryPassword(user) => Ok(user.username.clone()),
-            Auth::QueryToken(_) => Ok(res.json::<TokenResponse>().await?.username),
-            _ => unreachable!("This function may not be called with no authentication mode"),
-        },
-        status => Err(Error::Smarthome(status)),
+    /// Validates the client's credentials and returns a username
+    /// When using token authentication, the token's label is returned as well
+    /// When using session authentication, the obtained session cookie is stored in the client's cookie jar
+    pub(crate) async fn login_with_credentials(&self) -> Result<(String, Option<String>)> {
+        let mut login_url = self.smarthome_url.clone();
+        // Choose an adequate URL depending on the authentication mode
+        login_url.set_path(match &self.auth {
+            Auth::QueryToken(_) => "/api/login/token",
+            Auth::QueryPassword(_) | Auth::Session(_) => "/api/login",
+            Auth::None => unreachable!("login may not be called when using auth method `None`"),
+        });
+        // Perform the request
+        let req = self.client.request(Method::POST, login_url);
+        let req = match &self.auth {
+            Auth::QueryPassword(user) | Auth::Session(user) => req.json(&user),
+            Auth::QueryToken(token) => req.json(&Token {
+                token: token.to_string(),
+            }),
+            Auth::None => unreachable!("login may not be called when using auth method `None`"),
+        };
+        // Logging in has no side effects, so it is retried regardless of the method
+        let res = request::send_with_retries(
+            &self.client,
+            self.cassette.as_ref(),
+            &self.retry_policy,
+            req.build()?,
+            true,
+        )
+        .await?;
+        // Handle smarthome-errors which could occur during login
+        match res.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => match &self.auth {
+                Auth::QueryPassword(user) => Ok((user.username.clone(), None)),
+                Auth::Session(user) => {
+                    self.cookie_jar
+                        .set_cookies(&mut res.headers().get_all(SET_COOKIE).iter(), res.url());
+                    Ok((user.username.clone(), None))
+                }
+                Auth::QueryToken(_) => {
+                    let token = res.json::<TokenResponse>().await?;
+                    Ok((token.username, Some(token.token_label)))
+                }
+                Auth::None => {
+                    unreachable!("This function may not be called with no authentication mode")
+                }
+            },
+            _ => Err(Error::from_response(Method::POST, res).await),
+        }
     }
 }
@@ -1,9 +1,8 @@
-use reqwest::Method;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{errors::Result, Client, Error};
+use crate::{endpoint::Endpoint, errors::Result, Client, HardwareNode, Permission};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DebugInfoData {
     #[serde(rename = "version")]
@@ -27,7 +26,7 @@ pub struct DebugInfoData {
     pub time: ServerTime,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DatabaseStats {
     pub open_connections: i32,
@@ -37,7 +36,7 @@ pub struct DatabaseStats {
     pub idle: i32,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct PowerJob {
     pub id: i64,
@@ -45,22 +44,13 @@ pub struct PowerJob {
     pub power: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct JobResult {
     pub id: i64,
     pub error: String,
 }
 
-#[derive(Deserialize, Debug)]
-pub struct HardwareNode {
-    pub name: String,
-    pub online: bool,
-    pub enabled: bool,
-    pub url: String,
-    pub token: String,
-}
-
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct ServerTime {
     pub hours: u8,
     pub minutes: u8,
@@ -70,13 +60,11 @@ pub struct ServerTime {
 
 impl Client {
     pub async fn debug_info(&self) -> Result<DebugInfoData> {
-        let response = self
-            .client
-            .execute(self.build_request::<Option<()>>(Method::GET, "/api/debug", None)?)
-            .await?;
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(response.json::<DebugInfoData>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::get("/api/debug")
+                .json::<DebugInfoData>()
+                .permission(Permission::Debug),
+        )
+        .await
     }
 }
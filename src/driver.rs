@@ -1,10 +1,10 @@
-use reqwest::{Method, StatusCode};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::endpoint::Endpoint;
 use crate::errors::{Error, Result};
-use crate::Client;
+use crate::{Client, HomescriptExecError, Permission};
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct RichDriverResponse {
     pub driver: DriverData,
@@ -14,10 +14,10 @@ pub struct RichDriverResponse {
     /// This is `null` if the driver has not been configured yet.
     pub configuration: serde_json::Value,
     pub is_valid: bool,
-    pub validation_errors: Vec<serde_json::Value>,
+    pub validation_errors: Vec<HomescriptExecError>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DriverData {
     pub vendor_id: String,
@@ -29,6 +29,46 @@ pub struct DriverData {
     pub dirty: bool,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDriverRequest {
+    pub vendor_id: String,
+    pub model_id: String,
+    pub name: String,
+    pub version: String,
+    pub homescript_code: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverIdRequest<'request> {
+    vendor_id: &'request str,
+    model_id: &'request str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverCodeRequest<'request> {
+    vendor_id: &'request str,
+    model_id: &'request str,
+    code: &'request str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverConfigurationRequest<'request> {
+    vendor_id: &'request str,
+    model_id: &'request str,
+    data: &'request serde_json::Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverValidationResponse {
+    is_valid: bool,
+    validation_errors: Vec<HomescriptExecError>,
+}
+
 impl Client {
     /// Lists all device drivers of the target system, including their
     /// singleton configuration.
@@ -38,23 +78,192 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.list_drivers().await.unwrap();
     /// }
     /// ```
     pub async fn list_drivers(&self) -> Result<Vec<RichDriverResponse>> {
-        let response = self
-            .client
-            .execute(self.build_request::<()>(
-                Method::GET,
-                "/api/system/hardware/driver/list",
-                None,
-            )?)
+        self.call(
+            Endpoint::get("/api/system/hardware/driver/list")
+                .json::<Vec<RichDriverResponse>>()
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Uploads a new device driver to the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, CreateDriverRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.create_driver(&CreateDriverRequest {
+    ///         vendor_id: "acme".to_string(),
+    ///         model_id: "relay".to_string(),
+    ///         name: "ACME Relay".to_string(),
+    ///         version: "1.0.0".to_string(),
+    ///         homescript_code: std::fs::read_to_string("relay.hms").unwrap(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_driver(&self, request: &CreateDriverRequest) -> Result<()> {
+        self.call(
+            Endpoint::post("/api/system/hardware/driver/add", request)
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Replaces the Homescript code of an existing device driver
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.modify_driver_code(
+    ///         "acme",
+    ///         "relay",
+    ///         &std::fs::read_to_string("relay.hms").unwrap(),
+    ///     ).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_driver_code(
+        &self,
+        vendor_id: &str,
+        model_id: &str,
+        code: &str,
+    ) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/hardware/driver/code",
+                DriverCodeRequest {
+                    vendor_id,
+                    model_id,
+                    code,
+                },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Deletes a device driver from the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_driver("acme", "relay").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_driver(&self, vendor_id: &str, model_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/system/hardware/driver/delete",
+                DriverIdRequest {
+                    vendor_id,
+                    model_id,
+                },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Stores the singleton configuration of a device driver
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.set_driver_configuration(
+    ///         "acme",
+    ///         "relay",
+    ///         serde_json::json!({ "host": "relay.box" }),
+    ///     ).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_driver_configuration(
+        &self,
+        vendor_id: &str,
+        model_id: &str,
+        configuration: serde_json::Value,
+    ) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/hardware/driver/configure",
+                DriverConfigurationRequest {
+                    vendor_id,
+                    model_id,
+                    data: &configuration,
+                },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Validates a singleton configuration against the driver's specification without storing it
+    /// If the configuration is invalid, `Error::DriverValidation` contains the reasons
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Error};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     match client.validate_driver_configuration(
+    ///         "acme",
+    ///         "relay",
+    ///         serde_json::json!({ "host": 42 }),
+    ///     ).await {
+    ///         Ok(()) => println!("configuration is valid"),
+    ///         Err(Error::DriverValidation(errors)) => {
+    ///             for error in errors {
+    ///                 println!("{error}");
+    ///             }
+    ///         }
+    ///         Err(err) => panic!("{err}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn validate_driver_configuration(
+        &self,
+        vendor_id: &str,
+        model_id: &str,
+        configuration: serde_json::Value,
+    ) -> Result<()> {
+        let result = self
+            .call(
+                Endpoint::post(
+                    "/api/system/hardware/driver/validate",
+                    DriverConfigurationRequest {
+                        vendor_id,
+                        model_id,
+                        data: &configuration,
+                    },
+                )
+                .json::<DriverValidationResponse>()
+                .permission(Permission::ModifyServerConfig),
+            )
             .await?;
-        match response.status() {
-            StatusCode::OK => Ok(response.json::<Vec<RichDriverResponse>>().await?),
-            status => Err(Error::Smarthome(status)),
+        match result.is_valid {
+            true => Ok(()),
+            false => Err(Error::DriverValidation(result.validation_errors)),
         }
     }
 }
@@ -0,0 +1,216 @@
+use std::marker::PhantomData;
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Response, StatusCode, Version};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::errors::{Error, Result};
+use crate::{Client, Permission};
+
+/// Describes a single call of the Smarthome API
+/// Every request of the SDK is sent using `Client::call`, which handles authentication, retries,
+/// status validation and decoding of both the response and the server's error body
+pub(crate) struct Endpoint<B, R = Empty> {
+    method: Method,
+    path: String,
+    body: Option<B>,
+    /// Status codes which indicate success, any other status is turned into an `Error`
+    accepted: &'static [StatusCode],
+    /// The permission which the server requires for this endpoint
+    /// It is attached to the error if the server denies access
+    permission: Option<Permission>,
+    headers: HeaderMap,
+    /// Whether the request starts a protocol upgrade, see `Endpoint::upgrade`
+    upgrade: bool,
+    response: PhantomData<fn() -> R>,
+}
+
+impl Endpoint<(), Empty> {
+    /// Creates an endpoint without a request body which expects `200 OK` and an empty response
+    pub(crate) fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+            accepted: &[StatusCode::OK],
+            permission: None,
+            headers: HeaderMap::new(),
+            upgrade: false,
+            response: PhantomData,
+        }
+    }
+
+    pub(crate) fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    pub(crate) fn post<B: Serialize>(path: impl Into<String>, body: B) -> Endpoint<B, Empty> {
+        Self::new(Method::POST, path).body(body)
+    }
+
+    pub(crate) fn put<B: Serialize>(path: impl Into<String>, body: B) -> Endpoint<B, Empty> {
+        Self::new(Method::PUT, path).body(body)
+    }
+
+    pub(crate) fn delete<B: Serialize>(path: impl Into<String>, body: B) -> Endpoint<B, Empty> {
+        Self::new(Method::DELETE, path).body(body)
+    }
+}
+
+impl<B: Serialize, R: ResponseBody> Endpoint<B, R> {
+    /// Sends the given value as the JSON body of the request
+    pub(crate) fn body<T: Serialize>(self, body: T) -> Endpoint<T, R> {
+        Endpoint {
+            method: self.method,
+            path: self.path,
+            body: Some(body),
+            accepted: self.accepted,
+            permission: self.permission,
+            headers: self.headers,
+            upgrade: self.upgrade,
+            response: PhantomData,
+        }
+    }
+
+    /// Replaces the response type of the endpoint
+    fn returns<T: ResponseBody>(self) -> Endpoint<B, T> {
+        Endpoint {
+            method: self.method,
+            path: self.path,
+            body: self.body,
+            accepted: self.accepted,
+            permission: self.permission,
+            headers: self.headers,
+            upgrade: self.upgrade,
+            response: PhantomData,
+        }
+    }
+
+    /// Decodes the response body as JSON
+    pub(crate) fn json<T: DeserializeOwned>(self) -> Endpoint<B, Json<T>> {
+        self.returns()
+    }
+
+    /// Returns the response body as text
+    pub(crate) fn text(self) -> Endpoint<B, Text> {
+        self.returns()
+    }
+
+    /// Returns the raw response body
+    pub(crate) fn binary(self) -> Endpoint<B, Binary> {
+        self.returns()
+    }
+
+    /// Overrides the status codes which indicate success, `200 OK` by default
+    pub(crate) fn accept(mut self, statuses: &'static [StatusCode]) -> Self {
+        self.accepted = statuses;
+        self
+    }
+
+    /// Declares the permission which is required by the server
+    pub(crate) fn permission(mut self, permission: Permission) -> Self {
+        self.permission = Some(permission);
+        self
+    }
+
+    pub(crate) fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sends the request using HTTP/1.1, which is required for protocol upgrades
+    /// The client's regular connections may use HTTP/2, so a connection of `Client::upgrade_client` is used instead
+    pub(crate) fn upgrade(mut self) -> Self {
+        self.upgrade = true;
+        self
+    }
+}
+
+/// Decodes the body of a successful response
+pub(crate) trait ResponseBody {
+    type Output;
+
+    fn decode(body: Bytes) -> Result<Self::Output>;
+}
+
+/// The response body is ignored
+pub(crate) struct Empty;
+
+impl ResponseBody for Empty {
+    type Output = ();
+
+    fn decode(_: Bytes) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub(crate) struct Json<T>(PhantomData<fn() -> T>);
+
+impl<T: DeserializeOwned> ResponseBody for Json<T> {
+    type Output = T;
+
+    fn decode(body: Bytes) -> Result<T> {
+        Ok(serde_json::from_slice(&body)?)
+    }
+}
+
+pub(crate) struct Text;
+
+impl ResponseBody for Text {
+    type Output = String;
+
+    fn decode(body: Bytes) -> Result<String> {
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+pub(crate) struct Binary;
+
+impl ResponseBody for Binary {
+    type Output = Bytes;
+
+    fn decode(body: Bytes) -> Result<Bytes> {
+        Ok(body)
+    }
+}
+
+impl Client {
+    /// Sends the endpoint's request and decodes the response
+    pub(crate) async fn call<B: Serialize, R: ResponseBody>(
+        &self,
+        endpoint: Endpoint<B, R>,
+    ) -> Result<R::Output> {
+        let response = self.call_raw(endpoint).await?;
+        R::decode(response.bytes().await?)
+    }
+
+    /// Sends the endpoint's request and returns the response once its status was validated
+    /// The body is not read, which allows protocol upgrades
+    pub(crate) async fn call_raw<B: Serialize, R: ResponseBody>(
+        &self,
+        endpoint: Endpoint<B, R>,
+    ) -> Result<Response> {
+        let mut request =
+            self.build_request(endpoint.method.clone(), &endpoint.path, endpoint.body)?;
+        request.headers_mut().extend(endpoint.headers);
+        let http = match endpoint.upgrade {
+            true => {
+                *request.version_mut() = Version::HTTP_11;
+                &self.upgrade_client
+            }
+            false => &self.client,
+        };
+
+        let response = self.execute_using(http, request).await?;
+        if endpoint.accepted.contains(&response.status()) {
+            return Ok(response);
+        }
+
+        let mut err = Error::from_response(endpoint.method, response).await;
+        if let Error::PermissionDenied(response) = &mut err {
+            response.permission = endpoint.permission;
+        }
+        Err(err)
+    }
+}
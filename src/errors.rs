@@ -1,8 +1,12 @@
 use std::fmt::Display;
 
-use reqwest::StatusCode;
+use reqwest::{Method, Response, StatusCode};
+use serde::Deserialize;
 
-use crate::SERVER_VERSION_REQUIREMENT;
+use crate::{
+    CassetteError, DeviceValidationError, HomescriptExecError, Permission,
+    SERVER_VERSION_REQUIREMENT,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -12,12 +16,100 @@ pub enum Error {
     UrlParse(url::ParseError),
     /// The actual request failed, mostly due to network errors
     Reqwest(reqwest::Error),
+    /// The server rejected the client's credentials or session
+    Unauthorized(Box<ResponseError>),
+    /// The current user lacks permission to access the requested resource
+    PermissionDenied(Box<ResponseError>),
+    /// The requested resource does not exist on the server
+    NotFound(Box<ResponseError>),
+    /// The requested action conflicts with other data on the server
+    Conflict(Box<ResponseError>),
+    /// The server rejected the data sent by the client
+    Validation(Box<ResponseError>),
     /// The Smarthome server responded with an unexpected status code
-    Smarthome(reqwest::StatusCode),
+    Smarthome(Box<ResponseError>),
     /// A semantic version number could not be parsed and thus is invalid
     VersionParse(semver::Error),
     /// The SDK cannot connect to a Server which is incompatible
     IncompatibleVersion(String),
+    /// A WebSocket connection failed or was closed unexpectedly
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    /// A message could not be encoded or decoded
+    Json(serde_json::Error),
+    /// A device action was rejected by the client before it was sent
+    DeviceValidation(DeviceValidationError),
+    /// A driver configuration does not match the driver's specification
+    DriverValidation(Vec<HomescriptExecError>),
+    /// The current user lacks a permission which was checked before sending a request
+    MissingPermission(Permission),
+    /// A request could not be recorded or replayed
+    Cassette(CassetteError),
+}
+
+/// The error body which is returned by the Smarthome server
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerError {
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub time: String,
+}
+
+/// Describes a request which was answered with an unexpected status code
+#[derive(Debug)]
+pub struct ResponseError {
+    pub method: Method,
+    /// The path of the endpoint which failed, for example `/api/homescript/add`
+    pub path: String,
+    pub status: StatusCode,
+    /// The decoded error body, `None` if the server did not send a valid one
+    pub body: Option<ServerError>,
+    /// The permission required by the endpoint, only set if access was denied
+    pub permission: Option<Permission>,
+}
+
+impl Error {
+    /// Creates an error from an unexpected response and decodes the server's error body
+    pub(crate) async fn from_response(method: Method, response: Response) -> Self {
+        let path = response.url().path().to_string();
+        let status = response.status();
+        let body = match response.bytes().await {
+            Ok(bytes) => serde_json::from_slice::<ServerError>(&bytes).ok(),
+            Err(_) => None,
+        };
+
+        let err = Box::new(ResponseError {
+            method,
+            path,
+            status,
+            body,
+            permission: None,
+        });
+
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(err),
+            StatusCode::FORBIDDEN => Self::PermissionDenied(err),
+            StatusCode::NOT_FOUND => Self::NotFound(err),
+            StatusCode::CONFLICT => Self::Conflict(err),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation(err),
+            _ => Self::Smarthome(err),
+        }
+    }
+
+    /// Returns the failed response's details if this error was caused by the server
+    pub fn response(&self) -> Option<&ResponseError> {
+        match self {
+            Error::Unauthorized(err)
+            | Error::PermissionDenied(err)
+            | Error::NotFound(err)
+            | Error::Conflict(err)
+            | Error::Validation(err)
+            | Error::Smarthome(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl From<reqwest::Error> for Error {
@@ -38,22 +130,75 @@ impl From<semver::Error> for Error {
     }
 }
 
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl Display for ResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ({})", self.method, self.path, self.status)?;
+        match &self.body {
+            Some(body) if !body.message.is_empty() && !body.error.is_empty() => {
+                write!(f, ": {}: {}", body.message, body.error)
+            }
+            Some(body) if !body.message.is_empty() => write!(f, ": {}", body.message),
+            Some(body) if !body.error.is_empty() => write!(f, ": {}", body.error),
+            _ => Ok(()),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let message = match self {
                     Error::UrlParse(err) =>
                         format!("Could not parse URL: {}", err),
                     Error::Reqwest(err) => format!("Request error: {err}"),
-                    Error::Smarthome(status_code) => format!("Smarthome error ({status_code}):\n{}", match *status_code {
-                        StatusCode::UNAUTHORIZED => "Login failed: invalid credentials\n => Validate your credentials",
-                        StatusCode::FORBIDDEN => "Access to this resource has been denied.\n => You are possibly lacking permission to access the requested resource",
-                        StatusCode::SERVICE_UNAVAILABLE => "Smarthome is currently unavailable\n => The server has significant issues and was unable to respond properly",
-                        StatusCode::CONFLICT => "The requested action conflicts with other data on the system\n => Identify those conflicts and repeat the current action",
-                        _ => "Unimplemented status code: please open an issue on Github here: (https://github.com/smarthome-go/sdk-rs)"
+                    Error::Unauthorized(err) => format!("Login failed: invalid credentials: {err}\n => Validate your credentials"),
+                    Error::PermissionDenied(err) => match &err.permission {
+                        Some(permission) => format!("Access to this resource has been denied: {err}\n => This resource requires the permission `{permission}`"),
+                        None => format!("Access to this resource has been denied: {err}\n => You are possibly lacking permission to access the requested resource"),
+                    },
+                    Error::NotFound(err) => format!("The requested resource does not exist: {err}"),
+                    Error::Conflict(err) => format!("The requested action conflicts with other data on the system: {err}\n => Identify those conflicts and repeat the current action"),
+                    Error::Validation(err) => format!("The server rejected the submitted data: {err}"),
+                    Error::Smarthome(err) => format!("Smarthome error: {err}{}", match err.status {
+                        StatusCode::SERVICE_UNAVAILABLE => "\n => Smarthome is currently unavailable: the server has significant issues and was unable to respond properly",
+                        StatusCode::INTERNAL_SERVER_ERROR => "\n => The server failed to process the request",
+                        _ => "",
                     }),
-                    Error::VersionParse(err) => panic!("Internal error: a version is invalid and could not be parsed: this is a bug and not your fault: {err}"),
-                    Error::IncompatibleVersion(server_version) => format!("Incompatible server version: the server version is `{server_version}` but this program requires `{}`", SERVER_VERSION_REQUIREMENT)
+                    Error::VersionParse(err) => format!("Could not parse version: {err}"),
+                    Error::IncompatibleVersion(server_version) => format!("Incompatible server version: the server version is `{server_version}` but this program requires `{}`", SERVER_VERSION_REQUIREMENT),
+                    Error::WebSocket(err) => format!("WebSocket error: {err}"),
+                    Error::Json(err) => format!("Could not process JSON: {err}"),
+                    Error::DeviceValidation(err) => format!("Invalid device action: {err}"),
+                    Error::MissingPermission(permission) => format!("Missing permission `{permission}`\n => Ask an administrator to grant you this permission"),
+                    Error::Cassette(err) => format!("Cassette error: {err}"),
+                    Error::DriverValidation(errors) => format!("Invalid driver configuration:\n{}", errors.iter().map(|err| err.to_string()).collect::<Vec<String>>().join("\n")),
         };
         write!(f, "{message}")
     }
 }
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UrlParse(err) => Some(err),
+            Error::Reqwest(err) => Some(err),
+            Error::VersionParse(err) => Some(err),
+            Error::WebSocket(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::Cassette(CassetteError::Io(err)) => Some(err),
+            Error::Cassette(CassetteError::Format(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
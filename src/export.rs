@@ -1,9 +1,9 @@
-use reqwest::{Method, StatusCode};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::{
-    errors::{Error, Result},
-    Client,
+    endpoint::Endpoint, errors::Result, Camera, Client, HomescriptData, Permission, RoomData,
+    ShallowDeviceResponse,
 };
 
 #[derive(Serialize)]
@@ -13,33 +13,178 @@ pub struct ExportRequest {
     pub include_cache_data: bool,
 }
 
+/// The typed representation of an `export.json` file
+/// Fields which are not modeled by the SDK are preserved in `other`, so that an import restores them
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemExport {
+    #[serde(default)]
+    pub users: Vec<ExportUser>,
+    #[serde(default)]
+    pub rooms: Vec<ExportRoom>,
+    #[serde(default)]
+    pub devices: Vec<ExportEntry<ShallowDeviceResponse>>,
+    #[serde(default)]
+    pub hardware_nodes: Vec<ExportHardwareNode>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportUser {
+    pub data: ExportUserData,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub device_permissions: Vec<String>,
+    #[serde(default)]
+    pub camera_permissions: Vec<String>,
+    #[serde(default)]
+    pub homescripts: Vec<ExportEntry<HomescriptData>>,
+    #[serde(default)]
+    pub automations: Vec<ExportAutomation>,
+    #[serde(default)]
+    pub schedules: Vec<ExportSchedule>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportUserData {
+    pub username: String,
+    /// The user's hashed password
+    pub password: String,
+    pub forename: String,
+    pub surname: String,
+    pub primary_color_dark: String,
+    pub primary_color_light: String,
+    pub dark_theme: bool,
+    pub scheduler_enabled: bool,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRoom {
+    pub data: ExportEntry<RoomData>,
+    #[serde(default)]
+    pub cameras: Vec<ExportEntry<Camera>>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAutomation {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub homescript_id: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSchedule {
+    pub id: u64,
+    pub name: String,
+    pub hour: u8,
+    pub minute: u8,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportHardwareNode {
+    pub url: String,
+    pub name: String,
+    pub token: String,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// An SDK type which is part of an export
+/// Fields of the export which are not part of the SDK type are preserved in `other`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ExportEntry<T> {
+    #[serde(flatten)]
+    pub value: T,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
 impl Client {
     /// Fetches an `export.json` file from the Smarthome server
     /// ```rust no_run
-    /// use smarthome_sdk_rs::{Client, Auth};
+    /// use smarthome_sdk_rs::{Client, Auth, ExportRequest};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new(foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
-    ///     let res = client.export_config(ExportRequest {
-    //          include_profile_pictures: false,
-    //          include_cache_data: false,
+    ///     let res = client.export_config(&ExportRequest {
+    ///         include_profile_pictures: false,
+    ///         include_cache_data: false,
     ///     }).await.unwrap();
     /// }
     /// ```
     pub async fn export_config(&self, request: &ExportRequest) -> Result<String> {
-        let response = self
-            .client
-            .execute(self.build_request::<&ExportRequest>(
-                Method::POST,
-                "/api/system/config/export",
-                Some(request),
-            )?)
-            .await?;
-        match response.status() {
-            StatusCode::OK => Ok(response.text().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::post("/api/system/config/export", request)
+                .text()
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Fetches the server's export and decodes it into a `SystemExport`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, ExportRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let export = client.export_system(&ExportRequest {
+    ///         include_profile_pictures: false,
+    ///         include_cache_data: false,
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn export_system(&self, request: &ExportRequest) -> Result<SystemExport> {
+        Ok(serde_json::from_str(&self.export_config(request).await?)?)
+    }
+
+    /// Restores a previously exported configuration on the target server
+    /// Data on the server is replaced by the contents of the export
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, ExportRequest};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let staging = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     let production = Client::new("bar", Auth::None, true).await.unwrap();
+    ///
+    ///     let export = production.export_system(&ExportRequest {
+    ///         include_profile_pictures: true,
+    ///         include_cache_data: false,
+    ///     }).await.unwrap();
+    ///
+    ///     staging.import_config(&export).await.unwrap();
+    /// }
+    /// ```
+    pub async fn import_config(&self, export: &SystemExport) -> Result<()> {
+        self.call(
+            Endpoint::post("/api/system/config/import", export)
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
     }
 }
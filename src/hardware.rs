@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct HardwareNode {
+    pub name: String,
+    pub online: bool,
+    pub enabled: bool,
+    pub url: String,
+    pub token: String,
+}
+
+/// The configurable data of a hardware node
+#[derive(Serialize, Debug, Clone)]
+pub struct HardwareNodeData {
+    pub name: String,
+    pub url: String,
+    pub token: String,
+}
+
+#[derive(Serialize)]
+struct ModifyHardwareNodeRequest<'request> {
+    url: &'request str,
+    data: &'request HardwareNodeData,
+}
+
+#[derive(Serialize)]
+struct HardwareNodeUrlRequest<'request> {
+    url: &'request str,
+}
+
+#[derive(Serialize)]
+struct HardwareNodeStateRequest<'request> {
+    url: &'request str,
+    enabled: bool,
+}
+
+impl Client {
+    /// Returns all hardware nodes of the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let nodes = client.list_hardware_nodes().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_hardware_nodes(&self) -> Result<Vec<HardwareNode>> {
+        self.call(
+            Endpoint::get("/api/system/hardware/node/list")
+                .json::<Vec<HardwareNode>>()
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Adds a new hardware node to the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, HardwareNodeData};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.add_hardware_node(&HardwareNodeData {
+    ///         name: "Basement".to_string(),
+    ///         url: "http://node1.box".to_string(),
+    ///         token: "secret".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn add_hardware_node(&self, data: &HardwareNodeData) -> Result<()> {
+        self.call(
+            Endpoint::post("/api/system/hardware/node/add", data)
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Modifies the hardware node which is currently reachable using `url`
+    /// This can be used to rotate a node's token
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, HardwareNodeData};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.modify_hardware_node("http://node1.box", &HardwareNodeData {
+    ///         name: "Basement".to_string(),
+    ///         url: "http://node1.box".to_string(),
+    ///         token: "new-secret".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_hardware_node(&self, url: &str, data: &HardwareNodeData) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/hardware/node/modify",
+                ModifyHardwareNodeRequest { url, data },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Removes a hardware node from the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_hardware_node("http://node1.box").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_hardware_node(&self, url: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/system/hardware/node/delete",
+                HardwareNodeUrlRequest { url },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Enables or disables a hardware node, disabled nodes are ignored by the server
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     // Take the node out of service during maintenance
+    ///     client.set_hardware_node_enabled("http://node1.box", false).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_hardware_node_enabled(&self, url: &str, enabled: bool) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/hardware/node/state",
+                HardwareNodeStateRequest { url, enabled },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Lets the server check whether a hardware node is reachable and returns its updated state
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let node = client.check_hardware_node_health("http://node1.box").await.unwrap();
+    ///     println!("online: {}", node.online);
+    /// }
+    /// ```
+    pub async fn check_hardware_node_health(&self, url: &str) -> Result<HardwareNode> {
+        self.call(
+            Endpoint::post(
+                "/api/system/hardware/node/check",
+                HardwareNodeUrlRequest { url },
+            )
+            .json::<HardwareNode>()
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+}
@@ -1,21 +1,21 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{Client, Error, Result};
+use crate::{endpoint::Endpoint, Client, Permission, Result};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Homescript {
     pub owner: String,
     pub data: HomescriptData,
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum HomescriptType {
     Normal,
     Driver,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct HomescriptData {
     pub id: String,
@@ -39,11 +39,11 @@ struct DeleteHomescriptRequest<'request> {
 impl Client {
     /// Creates a new Homescript on the target server
     /// ```rust no_run
-    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData};
+    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData, HomescriptType};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     client.create_homescript(&HomescriptData {
     ///         id: "".to_string(),
@@ -54,32 +54,23 @@ impl Client {
     ///         workspace: "".to_string(),
     ///         scheduler_enabled: false,
     ///         quick_actions_enabled: false,
-    ///         is_widged: false,
+    ///         is_widget: false,
+    ///         type_: HomescriptType::Normal,
     ///     }).await.unwrap();
     /// }
     /// ```
     pub async fn create_homescript(&self, data: &HomescriptData) -> Result<()> {
-        let result = self
-            .client
-            .execute(self.build_request::<&HomescriptData>(
-                reqwest::Method::POST,
-                "/api/homescript/add",
-                Some(data),
-            )?)
-            .await?;
-        match result.status() {
-            reqwest::StatusCode::OK => Ok(()),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(Endpoint::post("/api/homescript/add", data).permission(Permission::Homescript))
+            .await
     }
 
     /// Modifies a Homescript's data
     /// ```rust no_run
-    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData};
+    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData, HomescriptType};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     client.create_homescript(&HomescriptData {
     ///         id: "".to_string(),
@@ -90,49 +81,35 @@ impl Client {
     ///         workspace: "".to_string(),
     ///         scheduler_enabled: false,
     ///         quick_actions_enabled: false,
-    ///         is_widged: false,
+    ///         is_widget: false,
+    ///         type_: HomescriptType::Normal,
     ///     }).await.unwrap();
     /// }
     /// ```
     pub async fn modify_homescript(&self, new_data: &HomescriptData) -> Result<()> {
-        let result = self
-            .client
-            .execute(self.build_request::<&HomescriptData>(
-                reqwest::Method::PUT,
-                "/api/homescript/modify",
-                Some(new_data),
-            )?)
-            .await?;
-        match result.status() {
-            reqwest::StatusCode::OK => Ok(()),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::put("/api/homescript/modify", new_data).permission(Permission::Homescript),
+        )
+        .await
     }
 
     /// Deletes a Homescript from the target server
     /// ```rust no_run
-    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData};
+    /// use smarthome_sdk_rs::{Client, Auth, HomescriptData, HomescriptType};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     client.delete_homescript("foo-id").await.unwrap();
     /// }
     /// ```
     pub async fn delete_homescript(&self, id: &str) -> Result<()> {
-        let result = self
-            .client
-            .execute(self.build_request::<DeleteHomescriptRequest>(
-                reqwest::Method::DELETE,
-                "/api/homescript/delete",
-                Some(DeleteHomescriptRequest { id }),
-            )?)
-            .await?;
-        match result.status() {
-            reqwest::StatusCode::OK => Ok(()),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::delete("/api/homescript/delete", DeleteHomescriptRequest { id })
+                .permission(Permission::Homescript),
+        )
+        .await
     }
 
     /// Returns a vec of the user's personal Homescripts
@@ -141,23 +118,17 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     client.list_personal_homescripts().await.unwrap();
     /// }
     /// ```
     pub async fn list_personal_homescripts(&self) -> Result<Vec<Homescript>> {
-        let result = self
-            .client
-            .execute(self.build_request::<()>(
-                reqwest::Method::GET,
-                "/api/homescript/list/personal",
-                None,
-            )?)
-            .await?;
-        match result.status() {
-            reqwest::StatusCode::OK => Ok(result.json::<Vec<Homescript>>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::get("/api/homescript/list/personal")
+                .json::<Vec<Homescript>>()
+                .permission(Permission::Homescript),
+        )
+        .await
     }
 }
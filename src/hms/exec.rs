@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
 use crate::client::Client;
-use crate::errors::{Error, Result};
+use crate::endpoint::{Endpoint, Json};
+use crate::errors::Result;
+use crate::Permission;
 
 pub enum HmsRunMode<'request> {
     Execute,
@@ -43,16 +46,19 @@ pub struct HomescriptArg<'request> {
     pub value: &'request str,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HomescriptExecResponse {
     pub success: bool,
     pub output: String,
     pub file_contents: HashMap<String, String>,
     pub errors: Vec<HomescriptExecError>,
+    /// Only reported by streaming runs, see `Client::run_homescript_streaming`
+    #[serde(default)]
+    pub exit_code: Option<i64>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HomescriptExecError {
     pub syntax_error: Option<SyntaxError>,
@@ -61,13 +67,13 @@ pub struct HomescriptExecError {
     pub span: HomescriptExecErrorSpan,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SyntaxError {
     pub message: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DiagnosticError {
     pub kind: u8,
@@ -75,7 +81,7 @@ pub struct DiagnosticError {
     pub notes: Vec<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RuntimeInterrupt {
     pub kind: String,
@@ -337,7 +343,7 @@ impl HomescriptExecError {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HomescriptExecErrorSpan {
     pub start: HomescriptExecErrorLocation,
@@ -345,7 +351,7 @@ pub struct HomescriptExecErrorSpan {
     pub filename: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HomescriptExecErrorLocation {
     pub line: usize,
@@ -356,16 +362,16 @@ pub struct HomescriptExecErrorLocation {
 impl Client {
     /// Executes Homescript code on the target server and returns the response
     /// ```rust no_run
-    /// use smarthome_sdk_rs::{Client, Auth};
+    /// use smarthome_sdk_rs::{Client, Auth, HmsRunMode};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.exec_homescript_code(
     ///             "println('Homescript is cool!')",
     ///             vec![], /* We dont need arguments for this example */
-    ///             false, /* If set to true, the code would only be linted instead of executed */
+    ///             HmsRunMode::Execute, /* Using `HmsRunMode::Lint`, the code would only be linted */
     ///     ).await.unwrap();
     /// }
     /// ```
@@ -375,33 +381,29 @@ impl Client {
         args: Vec<HomescriptArg<'_>>,
         run_mode: HmsRunMode<'_>,
     ) -> Result<HomescriptExecResponse> {
-        let req = match run_mode {
-            HmsRunMode::Execute => self.build_request::<RunHomescriptCodeRequest>(
-                reqwest::Method::POST,
-                "/api/homescript/run/live",
-                Some(RunHomescriptCodeRequest { code, args }),
-            )?,
+        match run_mode {
+            HmsRunMode::Execute => {
+                self.call(run_endpoint(
+                    "/api/homescript/run/live",
+                    RunHomescriptCodeRequest { code, args },
+                ))
+                .await
+            }
             HmsRunMode::Lint {
                 module_name,
                 is_driver,
-            } => self.build_request::<LintHomescriptCodeRequest>(
-                reqwest::Method::POST,
-                "/api/homescript/lint/live",
-                Some(LintHomescriptCodeRequest {
-                    code,
-                    args,
-                    module_name,
-                    is_driver,
-                }),
-            )?,
-        };
-
-        let result = self.client.execute(req).await?;
-        match result.status() {
-            reqwest::StatusCode::OK | reqwest::StatusCode::INTERNAL_SERVER_ERROR => {
-                Ok(result.json::<HomescriptExecResponse>().await?)
+            } => {
+                self.call(run_endpoint(
+                    "/api/homescript/lint/live",
+                    LintHomescriptCodeRequest {
+                        code,
+                        args,
+                        module_name,
+                        is_driver,
+                    },
+                ))
+                .await
             }
-            status => Err(Error::Smarthome(status)),
         }
     }
 
@@ -412,7 +414,7 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.exec_homescript(
     ///             "test-script",
@@ -431,19 +433,15 @@ impl Client {
             false => "/api/homescript/run",
             true => "/api/homescript/lint",
         };
-        let result = self
-            .client
-            .execute(self.build_request::<ExecHomescriptbyIdRequest>(
-                reqwest::Method::POST,
-                url,
-                Some(ExecHomescriptbyIdRequest { id, args }),
-            )?)
-            .await?;
-        match result.status() {
-            reqwest::StatusCode::OK | reqwest::StatusCode::INTERNAL_SERVER_ERROR => {
-                Ok(result.json::<HomescriptExecResponse>().await?)
-            }
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(run_endpoint(url, ExecHomescriptbyIdRequest { id, args }))
+            .await
     }
 }
+
+/// Failed runs are answered using `500` and still contain a regular result
+fn run_endpoint<B: Serialize>(path: &str, body: B) -> Endpoint<B, Json<HomescriptExecResponse>> {
+    Endpoint::post(path, body)
+        .json::<HomescriptExecResponse>()
+        .accept(&[StatusCode::OK, StatusCode::INTERNAL_SERVER_ERROR])
+        .permission(Permission::Homescript)
+}
@@ -3,3 +3,8 @@ pub use crud::*;
 
 mod exec;
 pub use exec::*;
+
+mod stream;
+pub use stream::*;
+
+mod storage;
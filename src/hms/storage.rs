@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::endpoint::Endpoint;
+use crate::errors::Result;
+use crate::Permission;
+
+#[derive(Deserialize)]
+struct StorageGetResponse {
+    value: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct StorageSetRequest<'request, T: Serialize> {
+    key: &'request str,
+    value: &'request T,
+}
+
+#[derive(Serialize)]
+struct StorageKeyRequest<'request> {
+    key: &'request str,
+}
+
+impl Client {
+    /// Reads a value from the current user's Homescript storage
+    /// Returns `None` if the key does not exist
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let counter: Option<u64> = client.storage_get("counter").await.unwrap();
+    /// }
+    /// ```
+    pub async fn storage_get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("key", key)
+            .finish();
+
+        let response = self
+            .call(
+                Endpoint::get(format!("/api/homescript/storage/get?{query}"))
+                    .json::<StorageGetResponse>()
+                    .permission(Permission::Homescript),
+            )
+            .await?;
+        match response.value {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes a value to the current user's Homescript storage, existing values are overwritten
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.storage_set("counter", &42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn storage_set<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/homescript/storage/set",
+                StorageSetRequest { key, value },
+            )
+            .permission(Permission::Homescript),
+        )
+        .await
+    }
+
+    /// Deletes a key from the current user's Homescript storage
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.storage_delete("counter").await.unwrap();
+    /// }
+    /// ```
+    pub async fn storage_delete(&self, key: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/homescript/storage/delete", StorageKeyRequest { key })
+                .permission(Permission::Homescript),
+        )
+        .await
+    }
+
+    /// Returns all keys and values of the current user's Homescript storage
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     for (key, value) in client.storage_list().await.unwrap() {
+    ///         println!("{key} = {value}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn storage_list(&self) -> Result<HashMap<String, serde_json::Value>> {
+        self.call(
+            Endpoint::get("/api/homescript/storage/list")
+                .json::<HashMap<String, serde_json::Value>>()
+                .permission(Permission::Homescript),
+        )
+        .await
+    }
+}
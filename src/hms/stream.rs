@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use reqwest::{
+    header::{
+        HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION,
+        UPGRADE,
+    },
+    StatusCode, Upgraded,
+};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::{
+    tungstenite::{
+        self,
+        error::ProtocolError,
+        handshake::{client::generate_key, derive_accept_key},
+        protocol::Role,
+        Message,
+    },
+    WebSocketStream,
+};
+
+use crate::client::Client;
+use crate::endpoint::Endpoint;
+use crate::errors::Result;
+use crate::{HomescriptArg, HomescriptExecError, HomescriptExecResponse, Permission};
+
+/// Specifies what is executed by a streaming Homescript run
+pub enum HmsRunTarget<'request> {
+    /// Runs an existing Homescript using its id
+    Id(&'request str),
+    /// Runs the given Homescript code
+    Code(&'request str),
+}
+
+/// An event which is emitted while a Homescript is running
+#[derive(Debug, Clone)]
+pub enum HomescriptRunEvent {
+    /// A chunk of the script's standard output
+    Output(String),
+    /// An error which occurred during execution
+    Error(HomescriptExecError),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum ClientMessage<'request> {
+    Init {
+        id: Option<&'request str>,
+        code: Option<&'request str>,
+        args: Vec<HomescriptArg<'request>>,
+    },
+    Stdin {
+        payload: &'request str,
+    },
+    Kill,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum ServerMessage {
+    Out { payload: String },
+    Err { error: HomescriptExecError },
+    Exit(ExitMessage),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExitMessage {
+    success: bool,
+    exit_code: i64,
+    #[serde(default)]
+    file_contents: HashMap<String, String>,
+}
+
+/// A running Homescript which is connected to the server using a WebSocket
+/// Output and errors are emitted as a `Stream` of `HomescriptRunEvent`s while the script runs
+pub struct HomescriptRun {
+    socket: WebSocketStream<Upgraded>,
+    output: String,
+    errors: Vec<HomescriptExecError>,
+    exit: Option<ExitMessage>,
+    closed: bool,
+}
+
+impl HomescriptRun {
+    /// Sends a line to the script's standard input
+    pub async fn send_stdin(&mut self, line: &str) -> Result<()> {
+        self.send(&ClientMessage::Stdin { payload: line }).await
+    }
+
+    /// Requests the server to terminate the script
+    /// The run still resolves to a response which contains the script's exit code
+    pub async fn kill(&mut self) -> Result<()> {
+        self.send(&ClientMessage::Kill).await
+    }
+
+    /// Waits until the script has finished and returns the final response
+    /// Remaining output and errors which have not been consumed yet are included in the response
+    pub async fn finish(mut self) -> Result<HomescriptExecResponse> {
+        while let Some(event) = self.next().await {
+            event?;
+        }
+
+        match self.exit {
+            Some(exit) => Ok(HomescriptExecResponse {
+                success: exit.success,
+                output: self.output,
+                file_contents: exit.file_contents,
+                errors: self.errors,
+                exit_code: Some(exit.exit_code),
+            }),
+            None => Err(tungstenite::Error::ConnectionClosed.into()),
+        }
+    }
+
+    async fn send(&mut self, message: &ClientMessage<'_>) -> Result<()> {
+        self.socket
+            .send(Message::Text(serde_json::to_string(message)?))
+            .await?;
+        Ok(())
+    }
+}
+
+impl Stream for HomescriptRun {
+    type Item = Result<HomescriptRunEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.exit.is_some() || self.closed {
+                return Poll::Ready(None);
+            }
+
+            let text = match ready!(self.socket.poll_next_unpin(cx)) {
+                Some(Ok(Message::Text(text))) => text,
+                // Pings are answered by the socket itself, other frames carry no information
+                Some(Ok(Message::Binary(_) | Message::Ping(_) | Message::Pong(_)))
+                | Some(Ok(Message::Frame(_))) => continue,
+                // The server closes the connection after the exit message, so an earlier close is an error
+                Some(Ok(Message::Close(_))) | None => {
+                    self.closed = true;
+                    return Poll::Ready(Some(Err(tungstenite::Error::ConnectionClosed.into())));
+                }
+                Some(Err(err)) => {
+                    self.closed = true;
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+            };
+
+            match serde_json::from_str::<ServerMessage>(&text) {
+                Ok(ServerMessage::Out { payload }) => {
+                    self.output.push_str(&payload);
+                    return Poll::Ready(Some(Ok(HomescriptRunEvent::Output(payload))));
+                }
+                Ok(ServerMessage::Err { error }) => {
+                    self.errors.push(error.clone());
+                    return Poll::Ready(Some(Ok(HomescriptRunEvent::Error(error))));
+                }
+                Ok(ServerMessage::Exit(exit)) => {
+                    self.exit = Some(exit);
+                    return Poll::Ready(None);
+                }
+                Err(err) => return Poll::Ready(Some(Err(err.into()))),
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Runs a Homescript using the server's WebSocket endpoint
+    /// In contrast to `exec_homescript`, output and errors are streamed while the script is running
+    /// The upgrade always uses an HTTP/1.1 connection, even if the server negotiates HTTP/2 for other requests
+    /// ```rust no_run
+    /// use futures_util::StreamExt;
+    /// use smarthome_sdk_rs::{Client, Auth, HmsRunTarget, HomescriptRunEvent};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let mut run = client.run_homescript_streaming(
+    ///             HmsRunTarget::Id("test-script"),
+    ///             vec![], /* We dont need arguments for this example */
+    ///     ).await.unwrap();
+    ///
+    ///     while let Some(event) = run.next().await {
+    ///         if let HomescriptRunEvent::Output(output) = event.unwrap() {
+    ///             print!("{output}");
+    ///         }
+    ///     }
+    ///
+    ///     let res = run.finish().await.unwrap();
+    /// }
+    /// ```
+    pub async fn run_homescript_streaming(
+        &self,
+        target: HmsRunTarget<'_>,
+        args: Vec<HomescriptArg<'_>>,
+    ) -> Result<HomescriptRun> {
+        let key = generate_key();
+        let response = self
+            .call_raw(
+                Endpoint::get("/api/homescript/run/ws")
+                    // WebSocket upgrades are only possible using HTTP/1.1
+                    .upgrade()
+                    .header(CONNECTION, HeaderValue::from_static("upgrade"))
+                    .header(UPGRADE, HeaderValue::from_static("websocket"))
+                    .header(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"))
+                    .header(
+                        SEC_WEBSOCKET_KEY,
+                        HeaderValue::from_str(&key).expect("generated keys are valid headers"),
+                    )
+                    .accept(&[StatusCode::SWITCHING_PROTOCOLS])
+                    .permission(Permission::Homescript),
+            )
+            .await?;
+
+        // Proves that the server understood the upgrade, instead of a proxy answering blindly
+        let expected = derive_accept_key(key.as_bytes());
+        let accept = response.headers().get(SEC_WEBSOCKET_ACCEPT);
+        if accept.map(HeaderValue::as_bytes) != Some(expected.as_bytes()) {
+            return Err(
+                tungstenite::Error::Protocol(ProtocolError::SecWebSocketAcceptKeyMismatch).into(),
+            );
+        }
+
+        let socket =
+            WebSocketStream::from_raw_socket(response.upgrade().await?, Role::Client, None).await;
+
+        let (id, code) = match target {
+            HmsRunTarget::Id(id) => (Some(id), None),
+            HmsRunTarget::Code(code) => (None, Some(code)),
+        };
+
+        let mut run = HomescriptRun {
+            socket,
+            output: String::new(),
+            errors: vec![],
+            exit: None,
+            closed: false,
+        };
+        run.send(&ClientMessage::Init { id, code, args }).await?;
+        Ok(run)
+    }
+}
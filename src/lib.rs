@@ -1,30 +1,62 @@
+mod builder;
 mod client;
 
 // Utility modules
 mod auth;
+mod cassette;
+mod endpoint;
 mod errors;
 mod request;
+mod retry;
 mod version;
 
 // Functionality modules
+mod automation;
 mod debug;
 mod driver;
 mod export;
+mod hardware;
 mod hms;
+mod logs;
+mod notification;
+mod permission;
 mod power;
+mod reminder;
 mod room;
+mod schedule;
+mod system;
+mod token;
+mod users;
+mod weather;
+
+#[cfg(feature = "mock")]
+pub mod mock;
 
 pub use auth::{Auth, User};
+pub use builder::ClientBuilder;
+pub use cassette::{CassetteError, CassetteMode};
 pub use client::Client;
+pub use retry::RetryPolicy;
 
 // Re-exports
+pub use automation::*;
 pub use debug::*;
 pub use driver::*;
 pub use errors::*;
 pub use export::*;
+pub use hardware::*;
 pub use hms::*;
+pub use logs::*;
+pub use notification::*;
+pub use permission::*;
 pub use power::*;
+pub use reminder::*;
 pub use room::*;
+pub use schedule::*;
+pub use system::*;
+pub use token::*;
+pub use users::*;
+pub use weather::*;
 
 /// This specifies the version constraints which are validated on a client's creation
 pub const SERVER_VERSION_REQUIREMENT: &str = ">=0.4.0";
@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LogEvent {
+    pub id: u64,
+    pub level: LogLevel,
+    pub name: String,
+    pub description: String,
+    /// Unix timestamp in milliseconds
+    pub time: u64,
+}
+
+/// The severity of a log event, encoded as a number by the server
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl From<LogLevel> for u8 {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => 0,
+            LogLevel::Debug => 1,
+            LogLevel::Info => 2,
+            LogLevel::Warn => 3,
+            LogLevel::Error => 4,
+            LogLevel::Fatal => 5,
+        }
+    }
+}
+
+impl TryFrom<u8> for LogLevel {
+    type Error = String;
+
+    fn try_from(value: u8) -> std::result::Result<Self, String> {
+        match value {
+            0 => Ok(LogLevel::Trace),
+            1 => Ok(LogLevel::Debug),
+            2 => Ok(LogLevel::Info),
+            3 => Ok(LogLevel::Warn),
+            4 => Ok(LogLevel::Error),
+            5 => Ok(LogLevel::Fatal),
+            other => Err(format!("invalid log level `{other}`")),
+        }
+    }
+}
+
+/// Restricts which log events are returned, fields which are `None` are not filtered
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Only events with at least this level are returned
+    pub min_level: Option<LogLevel>,
+    /// Only events which occurred at or after this unix timestamp in milliseconds are returned
+    pub after: Option<u64>,
+    /// Only events which occurred before this unix timestamp in milliseconds are returned
+    pub before: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FlushLogsRequest {
+    older_than: u64,
+}
+
+type PageFuture<'client> = Pin<Box<dyn Future<Output = Result<Vec<LogEvent>>> + Send + 'client>>;
+
+/// Fetches log events page by page, created using `Client::log_pages`
+/// The pager is a `Stream` of single events, the next page is only requested once the current one
+/// has been consumed. The stream ends after the first error.
+pub struct LogPager<'client> {
+    client: &'client Client,
+    filter: LogFilter,
+    page_size: usize,
+    offset: usize,
+    done: bool,
+    /// Events of the current page which have not been emitted by the stream yet
+    buffered: VecDeque<LogEvent>,
+    /// The request of the next page, if the stream is waiting for it
+    pending: Option<PageFuture<'client>>,
+}
+
+impl<'client> LogPager<'client> {
+    /// Returns the next page of log events or `None` if all events have been fetched
+    /// Events which were fetched but not yet emitted by the stream are returned first
+    pub async fn next_page(&mut self) -> Result<Option<Vec<LogEvent>>> {
+        if !self.buffered.is_empty() {
+            return Ok(Some(self.buffered.drain(..).collect()));
+        }
+        let page = match self.pending.take() {
+            Some(pending) => pending.await,
+            None if self.done => return Ok(None),
+            None => self.fetch_page().await,
+        };
+        self.complete_page(page)
+    }
+
+    fn fetch_page(&self) -> PageFuture<'client> {
+        let client = self.client;
+        let filter = self.filter.clone();
+        let page = (self.page_size, self.offset);
+        Box::pin(async move { client.fetch_logs(&filter, Some(page)).await })
+    }
+
+    /// Advances the offset, the pager is done once a page is not full
+    fn complete_page(&mut self, page: Result<Vec<LogEvent>>) -> Result<Option<Vec<LogEvent>>> {
+        let page = page?;
+        self.offset += page.len();
+        if page.len() < self.page_size {
+            self.done = true;
+        }
+
+        match page.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(page)),
+        }
+    }
+}
+
+impl Stream for LogPager<'_> {
+    type Item = Result<LogEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if self.done && self.pending.is_none() {
+                return Poll::Ready(None);
+            }
+
+            let pending = match &mut self.pending {
+                Some(pending) => pending,
+                None => {
+                    let pending = self.fetch_page();
+                    self.pending.insert(pending)
+                }
+            };
+            let page = ready!(pending.as_mut().poll(cx));
+            self.pending = None;
+
+            match self.complete_page(page) {
+                Ok(Some(page)) => self.buffered.extend(page),
+                Ok(None) => return Poll::Ready(None),
+                Err(err) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+    }
+}
+
+impl Client {
+    /// Returns all log events of the target system which match the filter
+    /// Requires the logs permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, LogFilter, LogLevel};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let errors = client.list_logs(&LogFilter {
+    ///         min_level: Some(LogLevel::Error),
+    ///         ..Default::default()
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_logs(&self, filter: &LogFilter) -> Result<Vec<LogEvent>> {
+        self.fetch_logs(filter, None).await
+    }
+
+    /// Returns a pager which fetches the log events matching the filter in pages of `page_size`
+    /// Requires the logs permission.
+    /// ```rust no_run
+    /// use futures_util::StreamExt;
+    /// use smarthome_sdk_rs::{Client, Auth, LogFilter};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let mut events = client.log_pages(LogFilter::default(), 100);
+    ///     while let Some(event) = events.next().await {
+    ///         let event = event.unwrap();
+    ///         println!("{}: {}", event.name, event.description);
+    ///     }
+    /// }
+    /// ```
+    pub fn log_pages(&self, filter: LogFilter, page_size: usize) -> LogPager<'_> {
+        LogPager {
+            client: self,
+            filter,
+            page_size: page_size.max(1),
+            offset: 0,
+            done: false,
+            buffered: VecDeque::new(),
+            pending: None,
+        }
+    }
+
+    /// Deletes all log events which are older than the given unix timestamp in milliseconds
+    /// Requires the logs permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.flush_logs(1700000000000).await.unwrap();
+    /// }
+    /// ```
+    pub async fn flush_logs(&self, older_than: u64) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/logs/delete/old", FlushLogsRequest { older_than })
+                .permission(Permission::Logs),
+        )
+        .await
+    }
+
+    /// Fetches log events, `page` optionally specifies the limit and offset
+    async fn fetch_logs(
+        &self,
+        filter: &LogFilter,
+        page: Option<(usize, usize)>,
+    ) -> Result<Vec<LogEvent>> {
+        self.call(
+            Endpoint::get(logs_path(filter, page))
+                .json::<Vec<LogEvent>>()
+                .permission(Permission::Logs),
+        )
+        .await
+    }
+}
+
+/// Builds the path of the log listing, the query is omitted if there is nothing to filter
+fn logs_path(filter: &LogFilter, page: Option<(usize, usize)>) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(level) = filter.min_level {
+        query.append_pair("level", &u8::from(level).to_string());
+    }
+    if let Some(after) = filter.after {
+        query.append_pair("after", &after.to_string());
+    }
+    if let Some(before) = filter.before {
+        query.append_pair("before", &before.to_string());
+    }
+    if let Some((limit, offset)) = page {
+        query.append_pair("limit", &limit.to_string());
+        query.append_pair("offset", &offset.to_string());
+    }
+
+    match query.finish() {
+        query if query.is_empty() => "/api/logs/list/all".to_string(),
+        query => format!("/api/logs/list/all?{query}"),
+    }
+}
@@ -0,0 +1,1125 @@
+//! An in-process Smarthome server for testing code which uses the SDK
+//!
+//! The server implements the endpoints used by `Client` on top of an in-memory `MockState`.
+//! Because the state can be inspected and edited at any time, tests become deterministic and
+//! do not depend on a live Smarthome instance.
+//! This module is only available when the `mock` feature is enabled.
+//!
+//! The mock covers authentication, devices, rooms and cameras, power usage, personal
+//! permissions, Homescripts including WebSocket runs, drivers, logs, debug information and
+//! configuration export and import.
+//! Every other endpoint, for instance automations, reminders, schedules, notifications, user,
+//! token and permission management, hardware nodes, Homescript storage, weather and the system
+//! configuration, is answered with `404 Not Found`.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use hyper::header::{
+    HeaderValue, CONNECTION, CONTENT_TYPE, COOKIE, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY,
+    SET_COOKIE, UPGRADE,
+};
+use hyper::http::request::Parts;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::upgrade::OnUpgrade;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::{
+    AuthToken, Camera, ConfigSpecWrapper, CreateDeviceRequest, CreateDriverRequest, DatabaseStats,
+    DebugInfoData, DeviceCapability, DeviceColor, DeviceDimmable, DeviceExtractions,
+    DevicePowerInformation, DeviceSensor, DriverData, Homescript, HomescriptData,
+    HomescriptExecResponse, HydratedDeviceResponse, LogEvent, Permission, PowerDrawPoint,
+    RichDriverResponse, Room, RoomData, ServerTime, ShallowDeviceResponse,
+};
+
+/// The name of the cookie which carries the session id
+const SESSION_COOKIE: &str = "session";
+
+/// A user which may log in to the mock server
+#[derive(Clone, Debug)]
+pub struct MockUser {
+    pub username: String,
+    pub password: String,
+    pub tokens: Vec<AuthToken>,
+}
+
+/// A request which was received by the mock server
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    /// The query string of the request, `None` if the URL contains no `?`
+    pub query: Option<String>,
+    /// The JSON body of the request, `Null` if there was none
+    pub body: serde_json::Value,
+}
+
+/// The data served by a `MockServer`
+/// Every field may be edited while the server is running, see `MockServer::state`
+#[derive(Clone, Debug)]
+pub struct MockState {
+    /// The Smarthome version reported by `/api/version`
+    pub version: String,
+    pub go_version: String,
+    /// If this is empty, authentication is disabled and every request is accepted
+    pub users: Vec<MockUser>,
+    /// Maps session ids to the username of their owner
+    /// Clearing this simulates expired sessions
+    pub sessions: HashMap<String, String>,
+    pub devices: Vec<HydratedDeviceResponse>,
+    /// Returned by both the daily and the complete power usage endpoint
+    pub power_usage: Vec<PowerDrawPoint>,
+    /// The permissions of each user, indexed by username
+    /// If authentication is disabled, requests are sent by the user `admin`
+    pub permissions: HashMap<String, Vec<Permission>>,
+    pub rooms: Vec<RoomData>,
+    pub cameras: Vec<Camera>,
+    /// Images returned by the camera feed endpoint, indexed by camera id
+    pub camera_feeds: HashMap<String, Bytes>,
+    pub homescripts: Vec<Homescript>,
+    /// The result returned by every Homescript run or lint
+    pub homescript_result: HomescriptExecResponse,
+    /// The messages sent by the WebSocket run endpoint once the run was initialized
+    /// If this is `None`, the messages are derived from `homescript_result`
+    /// The server closes the connection after the last message
+    pub homescript_messages: Option<Vec<serde_json::Value>>,
+    pub drivers: Vec<RichDriverResponse>,
+    pub debug_info: DebugInfoData,
+    /// Log events, in the order in which they are listed
+    pub logs: Vec<LogEvent>,
+    /// The document returned by the export endpoint, replaced on every import
+    pub export: serde_json::Value,
+    /// Statuses which are returned instead of serving the given path, consumed in order
+    /// This simulates temporary failures, for instance while the server is restarting
+    pub failures: HashMap<String, VecDeque<StatusCode>>,
+    /// Every request received by the server, in order
+    pub requests: Vec<MockRequest>,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self {
+            version: "0.10.0".to_string(),
+            go_version: "go1.21".to_string(),
+            users: vec![],
+            sessions: HashMap::new(),
+            devices: vec![],
+            power_usage: vec![],
+            permissions: HashMap::new(),
+            rooms: vec![],
+            cameras: vec![],
+            camera_feeds: HashMap::new(),
+            homescripts: vec![],
+            homescript_result: HomescriptExecResponse {
+                success: true,
+                output: String::new(),
+                file_contents: HashMap::new(),
+                errors: vec![],
+                exit_code: None,
+            },
+            homescript_messages: None,
+            drivers: vec![],
+            debug_info: DebugInfoData {
+                server_version: "0.10.0".to_string(),
+                go_version: "go1.21".to_string(),
+                cpu_cores: 1,
+                goroutines: 1,
+                memory_usage: 0,
+                database_online: true,
+                database_stats: DatabaseStats {
+                    open_connections: 0,
+                    in_use: 0,
+                    idle: 0,
+                },
+                power_job_count: 0,
+                power_job_with_error_count: 0,
+                power_jobs: vec![],
+                power_job_results: vec![],
+                hardware_nodes_count: 0,
+                hardware_nodes_online: 0,
+                hardware_nodes_enabled: 0,
+                hardware_nodes: vec![],
+                homescript_job_count: 0,
+                time: ServerTime {
+                    hours: 0,
+                    minutes: 0,
+                    seconds: 0,
+                    unix: 0,
+                },
+            },
+            logs: vec![],
+            export: serde_json::json!({}),
+            failures: HashMap::new(),
+            requests: vec![],
+        }
+    }
+}
+
+impl MockState {
+    /// Adds a user which can log in using the given password
+    pub fn with_user(mut self, username: &str, password: &str) -> Self {
+        self.users.push(MockUser {
+            username: username.to_string(),
+            password: password.to_string(),
+            tokens: vec![],
+        });
+        self
+    }
+
+    /// Adds an authentication token to an existing user
+    pub fn with_token(mut self, username: &str, token: &str, label: &str) -> Self {
+        if let Some(user) = self.users.iter_mut().find(|u| u.username == username) {
+            user.tokens.push(AuthToken {
+                token: token.to_string(),
+                label: label.to_string(),
+            });
+        }
+        self
+    }
+
+    /// Answers the next request to the given path using the given status
+    /// May be called repeatedly in order to fail several requests
+    pub fn with_failure(mut self, path: &str, status: StatusCode) -> Self {
+        self.failures
+            .entry(path.to_string())
+            .or_default()
+            .push_back(status);
+        self
+    }
+
+    /// Grants a permission to the given user
+    pub fn with_permission(mut self, username: &str, permission: Permission) -> Self {
+        self.permissions
+            .entry(username.to_string())
+            .or_default()
+            .push(permission);
+        self
+    }
+
+    /// Adds a device with the given power state to the given room
+    /// The device has neither dimmables nor sensors, the `power` capability is set
+    pub fn with_device(mut self, id: &str, room_id: &str, power: bool) -> Self {
+        let mut device = mock_device(id, room_id, DeviceCapability::Power);
+        device.extractions.power_information = Some(DevicePowerInformation {
+            state: power,
+            power_draw_watts: 0,
+        });
+        self.devices.push(device);
+        self
+    }
+
+    /// Adds a device with the given dimmables to the given room, the `dimmable` capability is set
+    pub fn with_dimmable_device(
+        mut self,
+        id: &str,
+        room_id: &str,
+        dimmables: Vec<DeviceDimmable>,
+    ) -> Self {
+        let mut device = mock_device(id, room_id, DeviceCapability::Dimmable);
+        device.extractions.dimmables = Some(dimmables);
+        self.devices.push(device);
+        self
+    }
+
+    /// Adds a device with the given color to the given room, the `color` capability is set
+    pub fn with_color_device(mut self, id: &str, room_id: &str, color: DeviceColor) -> Self {
+        let mut device = mock_device(id, room_id, DeviceCapability::Color);
+        device.extractions.color = Some(color);
+        self.devices.push(device);
+        self
+    }
+
+    /// Adds a device with the given sensors to the given room, the `sensor` capability is set
+    pub fn with_sensor_device(
+        mut self,
+        id: &str,
+        room_id: &str,
+        sensors: Vec<DeviceSensor>,
+    ) -> Self {
+        let mut device = mock_device(id, room_id, DeviceCapability::Sensor);
+        device.extractions.sensors = Some(sensors);
+        self.devices.push(device);
+        self
+    }
+
+    /// Adds an empty room
+    pub fn with_room(mut self, id: &str, name: &str) -> Self {
+        self.rooms.push(RoomData {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+        });
+        self
+    }
+
+    /// Returns the device with the given id
+    pub fn device(&self, id: &str) -> Option<&HydratedDeviceResponse> {
+        self.devices.iter().find(|d| d.shallow.id == id)
+    }
+}
+
+/// A Smarthome server running on a random local port
+/// The server is shut down once this value is dropped
+/// ```rust
+/// use smarthome_sdk_rs::mock::{MockServer, MockState};
+/// use smarthome_sdk_rs::{Auth, Client, User};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let server = MockServer::start(
+///         MockState::default()
+///             .with_user("admin", "admin")
+///             .with_room("living", "Living Room")
+///             .with_device("lamp", "living", false),
+///     )
+///     .await;
+///
+///     let client = Client::new(
+///         server.url(),
+///         Auth::Session(User {
+///             username: "admin".to_string(),
+///             password: "admin".to_string(),
+///         }),
+///         true,
+///     )
+///     .await
+///     .unwrap();
+///
+///     client.set_power("lamp", true).await.unwrap();
+///
+///     let state = server.state();
+///     assert!(state.device("lamp").unwrap().extractions.power_information.as_ref().unwrap().state);
+/// }
+/// ```
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Starts serving the given state on `127.0.0.1` using a random port
+    /// Must be called from within a Tokio runtime
+    ///
+    /// # Panics
+    /// Panics if no local port can be bound
+    pub async fn start(state: MockState) -> Self {
+        let listener =
+            TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).expect("bind mock server");
+        listener
+            .set_nonblocking(true)
+            .expect("configure mock server socket");
+        let url = format!(
+            "http://{}",
+            listener.local_addr().expect("read mock server address")
+        );
+
+        let state = Arc::new(Mutex::new(state));
+        let service_state = state.clone();
+        let service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle(state.clone(), request))) }
+        });
+
+        let (shutdown, shutdown_signal) = oneshot::channel::<()>();
+        let server = hyper::Server::from_tcp(listener)
+            .expect("start mock server")
+            .serve(service)
+            .with_graceful_shutdown(async {
+                shutdown_signal.await.ok();
+            });
+        tokio::spawn(server);
+
+        Self {
+            url,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// The base URL of the server, to be passed to `Client::new`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Grants access to the server's state
+    /// The server cannot answer requests while the returned guard is held
+    /// ```rust
+    /// use smarthome_sdk_rs::mock::{MockServer, MockState};
+    /// use smarthome_sdk_rs::{Auth, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MockServer::start(MockState::default()).await;
+    ///     let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+    ///     assert!(client.list_all_rooms().await.unwrap().is_empty());
+    ///
+    ///     server.state().rooms.push(smarthome_sdk_rs::RoomData {
+    ///         id: "kitchen".to_string(),
+    ///         name: "Kitchen".to_string(),
+    ///         description: String::new(),
+    ///     });
+    ///     assert_eq!(client.list_all_rooms().await.unwrap()[0].data.id, "kitchen");
+    /// }
+    /// ```
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+//
+// Request bodies which are not represented by public SDK types.
+//
+
+#[derive(Deserialize)]
+struct Credentials {
+    username: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct TokenCredentials {
+    token: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenLoginResponse<'response> {
+    username: &'response str,
+    token_label: &'response str,
+}
+
+#[derive(Deserialize)]
+struct IdBody {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceActionBody {
+    device_id: String,
+    power: Option<PowerBody>,
+    dim: Option<DimBody>,
+    color: Option<DeviceColor>,
+}
+
+#[derive(Deserialize)]
+struct PowerBody {
+    state: bool,
+}
+
+#[derive(Deserialize)]
+struct DimBody {
+    label: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverBody {
+    vendor_id: String,
+    model_id: String,
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DriverValidationBody<'response> {
+    is_valid: bool,
+    validation_errors: &'response [crate::HomescriptExecError],
+}
+
+/// An error which is sent to the client as a JSON response
+struct MockError {
+    status: StatusCode,
+    message: String,
+}
+
+impl MockError {
+    /// Builds a response which has the same shape as the errors sent by the real server
+    fn into_response(self) -> Response<Body> {
+        let body = serde_json::json!({
+            "message": self.message,
+            "error": self.status.canonical_reason().unwrap_or_default(),
+            "time": "",
+        });
+        let mut response = Response::new(Body::from(body.to_string()));
+        *response.status_mut() = self.status;
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    }
+}
+
+type MockResponse = std::result::Result<Response<Body>, MockError>;
+
+async fn handle(
+    state: Arc<Mutex<MockState>>,
+    request: Request<Body>,
+) -> std::result::Result<Response<Body>, Infallible> {
+    let (mut parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+
+    let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+    state.requests.push(MockRequest {
+        method: parts.method.clone(),
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().map(str::to_string),
+        body: serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+    });
+
+    if let Some(status) = state
+        .failures
+        .get_mut(parts.uri.path())
+        .and_then(VecDeque::pop_front)
+    {
+        return Ok(error(status, "simulated failure").into_response());
+    }
+
+    let response = match parts.uri.path() {
+        // The upgrade is taken from the request, which is why the socket is not part of `route`
+        "/api/homescript/run/ws" => state.homescript_socket(&mut parts),
+        _ => state.route(&parts, &body),
+    };
+    Ok(response.unwrap_or_else(MockError::into_response))
+}
+
+impl MockState {
+    fn route(&mut self, parts: &Parts, body: &[u8]) -> MockResponse {
+        let path = parts.uri.path();
+
+        // Endpoints which do not require authentication
+        match (&parts.method, path) {
+            (&Method::GET, "/api/version") => {
+                return json(&serde_json::json!({
+                    "version": self.version,
+                    "goVersion": self.go_version,
+                }))
+            }
+            (&Method::POST, "/api/login") => return self.login(body),
+            (&Method::POST, "/api/login/token") => return self.token_login(body),
+            _ => {}
+        }
+
+        let username = self.authenticate(parts)?;
+
+        if let Some(device_id) = path.strip_prefix("/api/devices/get/") {
+            return match (&parts.method, self.device(device_id)) {
+                (&Method::GET, Some(device)) => json(device),
+                (&Method::GET, None) => Err(not_found("device")),
+                _ => Err(error(StatusCode::METHOD_NOT_ALLOWED, "method not allowed")),
+            };
+        }
+        if let Some(camera_id) = path.strip_prefix("/api/camera/feed/") {
+            return match self.camera_feeds.get(camera_id) {
+                Some(image) => Ok(Response::new(Body::from(image.clone()))),
+                None => Err(not_found("camera")),
+            };
+        }
+
+        match (&parts.method, path) {
+            // Devices
+            (&Method::GET, "/api/devices/list/personal/rich" | "/api/devices/list/all/rich") => {
+                json(&self.devices)
+            }
+            (&Method::POST, "/api/devices/action/power") => self.device_action(body).map(|_| ok()),
+            (&Method::POST, "/api/devices/action/dim" | "/api/devices/action/color")
+            | (&Method::POST, "/api/devices/action/sensors") => {
+                let device = self.device_action(body)?;
+                json(&device)
+            }
+            (&Method::POST, "/api/devices/configure/add") => {
+                let request: CreateDeviceRequest = parse(body)?;
+                if self.device(&request.id).is_some() {
+                    return Err(conflict("device"));
+                }
+                self.devices.push(HydratedDeviceResponse {
+                    shallow: ShallowDeviceResponse {
+                        type_: request.type_,
+                        id: request.id,
+                        name: request.name,
+                        room_id: request.room_id,
+                        vendor_id: request.vendor_id,
+                        model_id: request.model_id,
+                        singleton_json: serde_json::Value::Null,
+                    },
+                    extractions: DeviceExtractions {
+                        hms_errors: vec![],
+                        config: ConfigSpecWrapper {
+                            capabilities: vec![DeviceCapability::Base],
+                            info: serde_json::Value::Null,
+                        },
+                        power_information: None,
+                        color: None,
+                        dimmables: None,
+                        sensors: None,
+                    },
+                });
+                Ok(ok())
+            }
+            (&Method::PUT, "/api/devices/configure/modify") => {
+                let request: ShallowDeviceResponse = parse(body)?;
+                let device = self
+                    .devices
+                    .iter_mut()
+                    .find(|d| d.shallow.id == request.id)
+                    .ok_or_else(|| not_found("device"))?;
+                device.shallow = request;
+                Ok(ok())
+            }
+            (&Method::DELETE, "/api/devices/configure/delete") => {
+                let request: IdBody = parse(body)?;
+                remove(&mut self.devices, |d| d.shallow.id == request.id, "device")
+            }
+            (&Method::GET, "/api/power/usage/day" | "/api/power/usage/all") => {
+                json(&self.power_usage)
+            }
+            (&Method::GET, "/api/user/permissions/personal") => json(
+                self.permissions
+                    .get(&username)
+                    .map_or(&[][..], Vec::as_slice),
+            ),
+            // Rooms and cameras
+            (&Method::GET, "/api/room/list/personal" | "/api/room/list/all") => {
+                json(&self.hydrated_rooms())
+            }
+            (&Method::POST, "/api/room/add") => {
+                let request: RoomData = parse(body)?;
+                insert(&mut self.rooms, request, |a, b| a.id == b.id, "room")
+            }
+            (&Method::PUT, "/api/room/modify") => {
+                let request: RoomData = parse(body)?;
+                replace(&mut self.rooms, request, |a, b| a.id == b.id, "room")
+            }
+            (&Method::DELETE, "/api/room/delete") => {
+                let request: IdBody = parse(body)?;
+                remove(&mut self.rooms, |r| r.id == request.id, "room")
+            }
+            (&Method::POST, "/api/camera/add") => {
+                let request: Camera = parse(body)?;
+                insert(&mut self.cameras, request, |a, b| a.id == b.id, "camera")
+            }
+            (&Method::PUT, "/api/camera/modify") => {
+                let request: Camera = parse(body)?;
+                replace(&mut self.cameras, request, |a, b| a.id == b.id, "camera")
+            }
+            (&Method::DELETE, "/api/camera/delete") => {
+                let request: IdBody = parse(body)?;
+                remove(&mut self.cameras, |c| c.id == request.id, "camera")
+            }
+            // Homescript
+            (&Method::GET, "/api/homescript/list/personal") => json(&self.homescripts),
+            (&Method::POST, "/api/homescript/add") => {
+                let request: HomescriptData = parse(body)?;
+                let homescript = Homescript {
+                    owner: username,
+                    data: request,
+                };
+                insert(
+                    &mut self.homescripts,
+                    homescript,
+                    |a, b| a.data.id == b.data.id,
+                    "homescript",
+                )
+            }
+            (&Method::PUT, "/api/homescript/modify") => {
+                let request: HomescriptData = parse(body)?;
+                let homescript = self
+                    .homescripts
+                    .iter_mut()
+                    .find(|h| h.data.id == request.id)
+                    .ok_or_else(|| not_found("homescript"))?;
+                homescript.data = request;
+                Ok(ok())
+            }
+            (&Method::DELETE, "/api/homescript/delete") => {
+                let request: IdBody = parse(body)?;
+                remove(
+                    &mut self.homescripts,
+                    |h| h.data.id == request.id,
+                    "homescript",
+                )
+            }
+            (&Method::POST, "/api/homescript/run" | "/api/homescript/lint") => {
+                let request: IdBody = parse(body)?;
+                if !self.homescripts.iter().any(|h| h.data.id == request.id) {
+                    return Err(not_found("homescript"));
+                }
+                self.homescript_response()
+            }
+            (&Method::POST, "/api/homescript/run/live" | "/api/homescript/lint/live") => {
+                self.homescript_response()
+            }
+            // Drivers
+            (&Method::GET, "/api/system/hardware/driver/list") => json(&self.drivers),
+            (&Method::POST, "/api/system/hardware/driver/add") => {
+                let request: CreateDriverRequest = parse(body)?;
+                let driver = RichDriverResponse {
+                    driver: DriverData {
+                        vendor_id: request.vendor_id,
+                        model_id: request.model_id,
+                        name: request.name,
+                        version: request.version,
+                        homescript_code: request.homescript_code,
+                        singleton_json: None,
+                        dirty: false,
+                    },
+                    info: serde_json::Value::Null,
+                    configuration: serde_json::Value::Null,
+                    is_valid: true,
+                    validation_errors: vec![],
+                };
+                insert(
+                    &mut self.drivers,
+                    driver,
+                    |a, b| {
+                        a.driver.vendor_id == b.driver.vendor_id
+                            && a.driver.model_id == b.driver.model_id
+                    },
+                    "driver",
+                )
+            }
+            (&Method::PUT, "/api/system/hardware/driver/code") => {
+                let request: DriverBody = parse(body)?;
+                self.driver(&request.vendor_id, &request.model_id)?
+                    .driver
+                    .homescript_code = request.code;
+                Ok(ok())
+            }
+            (&Method::PUT, "/api/system/hardware/driver/configure") => {
+                let request: DriverBody = parse(body)?;
+                self.driver(&request.vendor_id, &request.model_id)?
+                    .configuration = request.data;
+                Ok(ok())
+            }
+            (&Method::POST, "/api/system/hardware/driver/validate") => {
+                let request: DriverBody = parse(body)?;
+                let driver = self.driver(&request.vendor_id, &request.model_id)?;
+                json(&DriverValidationBody {
+                    is_valid: driver.is_valid,
+                    validation_errors: &driver.validation_errors,
+                })
+            }
+            (&Method::DELETE, "/api/system/hardware/driver/delete") => {
+                let request: DriverBody = parse(body)?;
+                remove(
+                    &mut self.drivers,
+                    |d| {
+                        d.driver.vendor_id == request.vendor_id
+                            && d.driver.model_id == request.model_id
+                    },
+                    "driver",
+                )
+            }
+            // System
+            (&Method::GET, "/api/debug") => json(&self.debug_info),
+            (&Method::GET, "/api/logs/list/all") => json(&self.filtered_logs(parts)?),
+            (&Method::POST, "/api/system/config/export") => json(&self.export),
+            (&Method::POST, "/api/system/config/import") => {
+                self.export = parse(body)?;
+                Ok(ok())
+            }
+            _ => Err(error(StatusCode::NOT_FOUND, "not found")),
+        }
+    }
+
+    fn login(&mut self, body: &[u8]) -> MockResponse {
+        let credentials: Credentials = parse(body)?;
+        if !self
+            .users
+            .iter()
+            .any(|u| u.username == credentials.username && u.password == credentials.password)
+        {
+            return Err(unauthorized());
+        }
+        let session_id = format!("mock-session-{}", self.requests.len());
+        let cookie = format!("{SESSION_COOKIE}={session_id}; Path=/; HttpOnly");
+        self.sessions.insert(session_id, credentials.username);
+
+        let mut response = empty(StatusCode::NO_CONTENT);
+        response.headers_mut().insert(
+            SET_COOKIE,
+            cookie.parse().expect("session cookie is a valid header"),
+        );
+        Ok(response)
+    }
+
+    fn token_login(&self, body: &[u8]) -> MockResponse {
+        let credentials: TokenCredentials = parse(body)?;
+        self.users
+            .iter()
+            .find_map(|user| {
+                user.tokens
+                    .iter()
+                    .find(|t| t.token == credentials.token)
+                    .map(|token| (user, token))
+            })
+            .map(|(user, token)| {
+                json(&TokenLoginResponse {
+                    username: &user.username,
+                    token_label: &token.label,
+                })
+            })
+            .unwrap_or_else(|| Err(unauthorized()))
+    }
+
+    /// Returns the name of the user who sent the request
+    /// Accepts query credentials, query tokens and session cookies
+    fn authenticate(&self, parts: &Parts) -> std::result::Result<String, MockError> {
+        if self.users.is_empty() {
+            return Ok("admin".to_string());
+        }
+
+        let query = query(parts);
+        if let (Some(username), Some(password)) = (query.get("username"), query.get("password")) {
+            if self
+                .users
+                .iter()
+                .any(|u| &u.username == username && &u.password == password)
+            {
+                return Ok(username.clone());
+            }
+        }
+        if let Some(token) = query.get("token") {
+            if let Some(user) = self
+                .users
+                .iter()
+                .find(|u| u.tokens.iter().any(|t| &t.token == token))
+            {
+                return Ok(user.username.clone());
+            }
+        }
+
+        let session = parts
+            .headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(|header| header.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == SESSION_COOKIE)
+            .and_then(|(_, id)| self.sessions.get(id));
+        match session {
+            Some(username) => Ok(username.clone()),
+            None => Err(unauthorized()),
+        }
+    }
+
+    /// Applies the power, dim or color action of the request and returns the updated device
+    fn device_action(
+        &mut self,
+        body: &[u8],
+    ) -> std::result::Result<HydratedDeviceResponse, MockError> {
+        let request: DeviceActionBody = parse(body)?;
+        let device = self
+            .devices
+            .iter_mut()
+            .find(|d| d.shallow.id == request.device_id)
+            .ok_or_else(|| not_found("device"))?;
+        let extractions = &mut device.extractions;
+
+        if let Some(power) = request.power {
+            extractions
+                .power_information
+                .as_mut()
+                .ok_or_else(|| unsupported("power"))?
+                .state = power.state;
+        }
+        if let Some(dim) = request.dim {
+            let dimmable = extractions
+                .dimmables
+                .iter_mut()
+                .flatten()
+                .find(|d| d.label == dim.label)
+                .ok_or_else(|| unsupported("dim"))?;
+            if !dimmable.range.contains(dim.value) {
+                return Err(error(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "dim value is out of range",
+                ));
+            }
+            dimmable.value = dim.value;
+        }
+        if let Some(color) = request.color {
+            extractions.color = Some(color);
+        }
+
+        Ok(device.clone())
+    }
+
+    fn driver(
+        &mut self,
+        vendor_id: &str,
+        model_id: &str,
+    ) -> std::result::Result<&mut RichDriverResponse, MockError> {
+        self.drivers
+            .iter_mut()
+            .find(|d| d.driver.vendor_id == vendor_id && d.driver.model_id == model_id)
+            .ok_or_else(|| not_found("driver"))
+    }
+
+    /// Applies the level, time and paging parameters of the request
+    fn filtered_logs(&self, parts: &Parts) -> std::result::Result<Vec<LogEvent>, MockError> {
+        let query = query(parts);
+        let number = |key: &str| {
+            query
+                .get(key)
+                .map(|value| value.parse::<u64>())
+                .transpose()
+                .map_err(|_| error(StatusCode::BAD_REQUEST, &format!("invalid `{key}`")))
+        };
+        let level = number("level")?;
+        let after = number("after")?;
+        let before = number("before")?;
+        let offset = number("offset")?.unwrap_or(0) as usize;
+        let limit = number("limit")?.map_or(usize::MAX, |limit| limit as usize);
+
+        Ok(self
+            .logs
+            .iter()
+            .filter(|event| level.is_none_or(|level| u8::from(event.level) as u64 >= level))
+            .filter(|event| after.is_none_or(|after| event.time >= after))
+            .filter(|event| before.is_none_or(|before| event.time < before))
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn hydrated_rooms(&self) -> Vec<Room> {
+        self.rooms
+            .iter()
+            .map(|room| Room {
+                data: room.clone(),
+                switches: self
+                    .devices
+                    .iter()
+                    .filter(|d| d.shallow.room_id == room.id)
+                    .cloned()
+                    .collect(),
+                cameras: self
+                    .cameras
+                    .iter()
+                    .filter(|c| c.room_id == room.id)
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Accepts the WebSocket upgrade and plays `homescript_messages` once the run is initialized
+    fn homescript_socket(&mut self, parts: &mut Parts) -> MockResponse {
+        self.authenticate(parts)?;
+        if parts.method != Method::GET {
+            return Err(error(StatusCode::METHOD_NOT_ALLOWED, "method not allowed"));
+        }
+        let (Some(key), Some(upgrade)) = (
+            parts.headers.get(SEC_WEBSOCKET_KEY),
+            parts.extensions.remove::<OnUpgrade>(),
+        ) else {
+            return Err(error(
+                StatusCode::BAD_REQUEST,
+                "expected a WebSocket upgrade",
+            ));
+        };
+        let accept = derive_accept_key(key.as_bytes());
+
+        let messages = self
+            .homescript_messages
+            .clone()
+            .unwrap_or_else(|| self.derived_homescript_messages());
+        tokio::spawn(async move {
+            let Ok(upgraded) = upgrade.await else {
+                return;
+            };
+            let mut socket = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+            // The first message of the client contains the script and its arguments
+            if !matches!(socket.next().await, Some(Ok(Message::Text(_)))) {
+                return;
+            }
+            for message in messages {
+                if socket
+                    .send(Message::Text(message.to_string()))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            socket.close(None).await.ok();
+        });
+
+        let mut response = empty(StatusCode::SWITCHING_PROTOCOLS);
+        let headers = response.headers_mut();
+        headers.insert(CONNECTION, HeaderValue::from_static("upgrade"));
+        headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+        headers.insert(
+            SEC_WEBSOCKET_ACCEPT,
+            accept.parse().expect("accept keys are valid headers"),
+        );
+        Ok(response)
+    }
+
+    /// Emits the output, the errors and the exit code of `homescript_result`
+    fn derived_homescript_messages(&self) -> Vec<serde_json::Value> {
+        let result = &self.homescript_result;
+        let output = (!result.output.is_empty())
+            .then(|| serde_json::json!({ "kind": "out", "payload": result.output }));
+        let errors = result
+            .errors
+            .iter()
+            .map(|error| serde_json::json!({ "kind": "err", "error": error }));
+        let exit = serde_json::json!({
+            "kind": "exit",
+            "success": result.success,
+            "exitCode": result.exit_code.unwrap_or(if result.success { 0 } else { 1 }),
+            "fileContents": result.file_contents,
+        });
+        output.into_iter().chain(errors).chain([exit]).collect()
+    }
+
+    /// Failed runs are reported using `500`, just like the real server does
+    fn homescript_response(&self) -> MockResponse {
+        let mut response = json(&self.homescript_result)?;
+        if !self.homescript_result.success {
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        }
+        Ok(response)
+    }
+}
+
+/// Creates a device with the `base` capability and the given one, all extractions are empty
+fn mock_device(id: &str, room_id: &str, capability: DeviceCapability) -> HydratedDeviceResponse {
+    HydratedDeviceResponse {
+        shallow: ShallowDeviceResponse {
+            type_: crate::DeviceType::Output,
+            id: id.to_string(),
+            name: id.to_string(),
+            room_id: room_id.to_string(),
+            vendor_id: "mock".to_string(),
+            model_id: "mock".to_string(),
+            singleton_json: serde_json::Value::Null,
+        },
+        extractions: DeviceExtractions {
+            hms_errors: vec![],
+            config: ConfigSpecWrapper {
+                capabilities: vec![DeviceCapability::Base, capability],
+                info: serde_json::Value::Null,
+            },
+            power_information: None,
+            color: None,
+            dimmables: None,
+            sensors: None,
+        },
+    }
+}
+
+//
+// Helpers for building responses.
+//
+
+fn query(parts: &Parts) -> HashMap<String, String> {
+    url::form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+        .into_owned()
+        .collect()
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> std::result::Result<T, MockError> {
+    serde_json::from_slice(body)
+        .map_err(|err| error(StatusCode::BAD_REQUEST, &format!("bad request: {err}")))
+}
+
+fn insert<T>(items: &mut Vec<T>, item: T, same: fn(&T, &T) -> bool, kind: &str) -> MockResponse {
+    if items.iter().any(|existing| same(existing, &item)) {
+        return Err(conflict(kind));
+    }
+    items.push(item);
+    Ok(ok())
+}
+
+fn replace<T>(items: &mut [T], item: T, same: fn(&T, &T) -> bool, kind: &str) -> MockResponse {
+    let existing = items
+        .iter_mut()
+        .find(|existing| same(existing, &item))
+        .ok_or_else(|| not_found(kind))?;
+    *existing = item;
+    Ok(ok())
+}
+
+fn remove<T>(items: &mut Vec<T>, matches: impl Fn(&T) -> bool, kind: &str) -> MockResponse {
+    let len = items.len();
+    items.retain(|item| !matches(item));
+    match items.len() == len {
+        true => Err(not_found(kind)),
+        false => Ok(ok()),
+    }
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> MockResponse {
+    let body = serde_json::to_vec(value)
+        .map_err(|err| error(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()))?;
+    let mut response = Response::new(Body::from(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Ok(response)
+}
+
+fn empty(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+fn ok() -> Response<Body> {
+    empty(StatusCode::OK)
+}
+
+fn error(status: StatusCode, message: &str) -> MockError {
+    MockError {
+        status,
+        message: message.to_string(),
+    }
+}
+
+fn unauthorized() -> MockError {
+    error(StatusCode::UNAUTHORIZED, "invalid credentials")
+}
+
+fn not_found(kind: &str) -> MockError {
+    error(StatusCode::NOT_FOUND, &format!("{kind} does not exist"))
+}
+
+fn conflict(kind: &str) -> MockError {
+    error(StatusCode::CONFLICT, &format!("{kind} already exists"))
+}
+
+fn unsupported(action: &str) -> MockError {
+    error(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        &format!("device does not support the `{action}` action"),
+    )
+}
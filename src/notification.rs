@@ -0,0 +1,178 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: u64,
+    pub priority: NotificationPriority,
+    pub title: String,
+    pub description: String,
+    /// Unix timestamp in milliseconds
+    pub date: u64,
+}
+
+/// The priority of a notification, encoded as a number by the server
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum NotificationPriority {
+    Info,
+    Warning,
+    Error,
+}
+
+impl From<NotificationPriority> for u8 {
+    fn from(priority: NotificationPriority) -> Self {
+        match priority {
+            NotificationPriority::Info => 1,
+            NotificationPriority::Warning => 2,
+            NotificationPriority::Error => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for NotificationPriority {
+    type Error = String;
+
+    fn try_from(value: u8) -> std::result::Result<Self, String> {
+        match value {
+            1 => Ok(NotificationPriority::Info),
+            2 => Ok(NotificationPriority::Warning),
+            3 => Ok(NotificationPriority::Error),
+            other => Err(format!("invalid notification priority `{other}`")),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct NotificationCountResponse {
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct DeleteNotificationRequest {
+    id: u64,
+}
+
+#[derive(Serialize)]
+struct NotifyUserRequest<'request> {
+    username: &'request str,
+    title: &'request str,
+    description: &'request str,
+    priority: NotificationPriority,
+}
+
+impl Client {
+    /// Returns the notifications of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let notifications = client.list_notifications().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_notifications(&self) -> Result<Vec<Notification>> {
+        self.call(Endpoint::get("/api/user/notification/list").json::<Vec<Notification>>())
+            .await
+    }
+
+    /// Returns the number of notifications of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let count = client.notification_count().await.unwrap();
+    /// }
+    /// ```
+    pub async fn notification_count(&self) -> Result<u64> {
+        Ok(self
+            .call(Endpoint::get("/api/user/notification/count").json::<NotificationCountResponse>())
+            .await?
+            .count)
+    }
+
+    /// Deletes a notification of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_notification(42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_notification(&self, id: u64) -> Result<()> {
+        self.call(Endpoint::delete(
+            "/api/user/notification/delete",
+            DeleteNotificationRequest { id },
+        ))
+        .await
+    }
+
+    /// Deletes all notifications of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_all_notifications().await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_all_notifications(&self) -> Result<()> {
+        self.call(Endpoint::new(
+            Method::DELETE,
+            "/api/user/notification/delete/all",
+        ))
+        .await
+    }
+
+    /// Sends a notification to the given user
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, NotificationPriority};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.notify_user(
+    ///         "admin",
+    ///         "Backup finished",
+    ///         "The nightly backup completed successfully",
+    ///         NotificationPriority::Info,
+    ///     ).await.unwrap();
+    /// }
+    /// ```
+    pub async fn notify_user(
+        &self,
+        username: &str,
+        title: &str,
+        description: &str,
+        priority: NotificationPriority,
+    ) -> Result<()> {
+        self.call(
+            Endpoint::post(
+                "/api/user/notification/send",
+                NotifyUserRequest {
+                    username,
+                    title,
+                    description,
+                    priority,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    endpoint::Endpoint,
+    errors::{Error, Result},
+    Client,
+};
+
+/// A permission which can be granted to a user
+/// Identifiers which are unknown to the SDK are preserved using `Permission::Other`
+/// Permissions are compared using their identifiers, so `Other` matches the equivalent variant
+/// ```rust
+/// use smarthome_sdk_rs::Permission;
+///
+/// assert_eq!(Permission::Other("setPower".to_string()), Permission::SetPower);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(from = "String", into = "String")]
+pub enum Permission {
+    /// Grants every permission
+    Wildcard,
+    Authentication,
+    SetPower,
+    PowerUsage,
+    ViewCameras,
+    Homescript,
+    Automation,
+    Scheduler,
+    Reminder,
+    ModifyRooms,
+    ModifyUsers,
+    /// Also referred to as the system-config permission
+    ModifyServerConfig,
+    Logs,
+    Debug,
+    Other(String),
+}
+
+impl Permission {
+    /// Returns the identifier which is used by the server
+    pub fn as_str(&self) -> &str {
+        match self {
+            Permission::Wildcard => "*",
+            Permission::Authentication => "authentication",
+            Permission::SetPower => "setPower",
+            Permission::PowerUsage => "powerUsage",
+            Permission::ViewCameras => "viewCameras",
+            Permission::Homescript => "homescript",
+            Permission::Automation => "automation",
+            Permission::Scheduler => "scheduler",
+            Permission::Reminder => "reminder",
+            Permission::ModifyRooms => "modifyRooms",
+            Permission::ModifyUsers => "modifyUsers",
+            Permission::ModifyServerConfig => "modifyServerConfig",
+            Permission::Logs => "logs",
+            Permission::Debug => "debug",
+            Permission::Other(permission) => permission,
+        }
+    }
+}
+
+impl PartialEq for Permission {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Permission {}
+
+impl Hash for Permission {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl From<String> for Permission {
+    fn from(permission: String) -> Self {
+        match permission.as_str() {
+            "*" => Permission::Wildcard,
+            "authentication" => Permission::Authentication,
+            "setPower" => Permission::SetPower,
+            "powerUsage" => Permission::PowerUsage,
+            "viewCameras" => Permission::ViewCameras,
+            "homescript" => Permission::Homescript,
+            "automation" => Permission::Automation,
+            "scheduler" => Permission::Scheduler,
+            "reminder" => Permission::Reminder,
+            "modifyRooms" => Permission::ModifyRooms,
+            "modifyUsers" => Permission::ModifyUsers,
+            "modifyServerConfig" => Permission::ModifyServerConfig,
+            "logs" => Permission::Logs,
+            "debug" => Permission::Debug,
+            _ => Permission::Other(permission),
+        }
+    }
+}
+
+impl From<Permission> for String {
+    fn from(permission: Permission) -> Self {
+        match permission {
+            Permission::Other(permission) => permission,
+            permission => permission.as_str().to_string(),
+        }
+    }
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Serialize)]
+struct PermissionRequest<'request> {
+    username: &'request str,
+    permission: &'request Permission,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DevicePermissionRequest<'request> {
+    username: &'request str,
+    device_id: &'request str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CameraPermissionRequest<'request> {
+    username: &'request str,
+    camera_id: &'request str,
+}
+
+impl Client {
+    /// Returns the permissions of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let permissions = client.personal_permissions().await.unwrap();
+    /// }
+    /// ```
+    pub async fn personal_permissions(&self) -> Result<Vec<Permission>> {
+        self.call(Endpoint::get("/api/user/permissions/personal").json::<Vec<Permission>>())
+            .await
+    }
+
+    /// Returns the permissions of the given user
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let permissions = client.list_user_permissions("alice").await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_user_permissions(&self, username: &str) -> Result<Vec<Permission>> {
+        self.call(
+            Endpoint::get(format!("/api/user/permissions/list/{username}"))
+                .json::<Vec<Permission>>()
+                .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Checks whether the current user has the given permission
+    /// The wildcard permission implies every other permission
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Permission};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     if !client.has_permission(&Permission::ModifyServerConfig).await.unwrap() {
+    ///         eprintln!("This tool requires the system-config permission");
+    ///     }
+    /// }
+    /// ```
+    pub async fn has_permission(&self, permission: &Permission) -> Result<bool> {
+        Ok(self
+            .personal_permissions()
+            .await?
+            .iter()
+            .any(|granted| granted == permission || *granted == Permission::Wildcard))
+    }
+
+    /// Returns `Error::MissingPermission` if the current user lacks the given permission
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Permission};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.require_permission(Permission::ModifyServerConfig).await.unwrap();
+    ///     let drivers = client.list_drivers().await.unwrap();
+    /// }
+    /// ```
+    pub async fn require_permission(&self, permission: Permission) -> Result<()> {
+        match self.has_permission(&permission).await? {
+            true => Ok(()),
+            false => Err(Error::MissingPermission(permission)),
+        }
+    }
+
+    /// Grants a permission to the given user
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Permission};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.grant_permission("alice", &Permission::SetPower).await.unwrap();
+    /// }
+    /// ```
+    pub async fn grant_permission(&self, username: &str, permission: &Permission) -> Result<()> {
+        self.call(
+            Endpoint::post(
+                "/api/user/permissions/add",
+                PermissionRequest {
+                    username,
+                    permission,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Revokes a permission from the given user
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Permission};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.revoke_permission("alice", &Permission::SetPower).await.unwrap();
+    /// }
+    /// ```
+    pub async fn revoke_permission(&self, username: &str, permission: &Permission) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/user/permissions/delete",
+                PermissionRequest {
+                    username,
+                    permission,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Allows the given user to access a device
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.grant_device_permission("alice", "desk_lamp").await.unwrap();
+    /// }
+    /// ```
+    pub async fn grant_device_permission(&self, username: &str, device_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::post(
+                "/api/user/permissions/device/add",
+                DevicePermissionRequest {
+                    username,
+                    device_id,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Revokes the given user's access to a device
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.revoke_device_permission("alice", "desk_lamp").await.unwrap();
+    /// }
+    /// ```
+    pub async fn revoke_device_permission(&self, username: &str, device_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/user/permissions/device/delete",
+                DevicePermissionRequest {
+                    username,
+                    device_id,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Allows the given user to view a camera
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.grant_camera_permission("alice", "office_cam").await.unwrap();
+    /// }
+    /// ```
+    pub async fn grant_camera_permission(&self, username: &str, camera_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::post(
+                "/api/user/permissions/camera/add",
+                CameraPermissionRequest {
+                    username,
+                    camera_id,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Revokes the given user's access to a camera
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.revoke_camera_permission("alice", "office_cam").await.unwrap();
+    /// }
+    /// ```
+    pub async fn revoke_camera_permission(&self, username: &str, camera_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/user/permissions/camera/delete",
+                CameraPermissionRequest {
+                    username,
+                    camera_id,
+                },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+}
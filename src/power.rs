@@ -1,14 +1,18 @@
-use reqwest::{Method, StatusCode};
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 
+use crate::endpoint::Endpoint;
 use crate::errors::{Error, Result};
-use crate::{Client, HomescriptExecError};
+use crate::{Client, HomescriptExecError, Permission};
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct DeviceRequest<'request> {
     device_id: &'request str,
     power: Option<DevicePowerRequest>,
+    dim: Option<DeviceDimRequest<'request>>,
+    color: Option<DeviceColor>,
 }
 
 #[derive(Serialize)]
@@ -17,18 +21,25 @@ struct DevicePowerRequest {
     state: bool,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceDimRequest<'request> {
+    label: &'request str,
+    value: f64,
+}
+
 //
 // DEVICE.
 //
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct HydratedDeviceResponse {
     pub shallow: ShallowDeviceResponse,
     pub extractions: DeviceExtractions,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceExtractions {
     pub hms_errors: Vec<HomescriptExecError>,
@@ -39,21 +50,21 @@ pub struct DeviceExtractions {
     pub sensors: Option<Vec<DeviceSensor>>,
 }
 
-#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct DeviceColor {
     pub r: u8,
     pub g: u8,
     pub b: u8,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfigSpecWrapper {
     pub capabilities: Vec<DeviceCapability>,
     pub info: serde_json::Value,
 }
 
-#[derive(Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum DeviceCapability {
     Base,
@@ -66,14 +77,14 @@ pub enum DeviceCapability {
     Unknown,
 }
 
-#[derive(Deserialize, Clone, Copy, Debug)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum DeviceType {
     Input,
     Output,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ShallowDeviceResponse {
     #[serde(rename = "type")]
@@ -86,29 +97,28 @@ pub struct ShallowDeviceResponse {
     pub singleton_json: serde_json::Value,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DevicePowerInformation {
     pub state: bool,
     pub power_draw_watts: usize,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DeviceDimmableRange {
-    lower: f64,
-    // Upper is always exclusive.
-    upper: f64,
-}
-
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DimmableRange {
     pub lower: f64,
     pub upper: f64,
 }
 
-#[derive(Deserialize, Clone)]
+impl DimmableRange {
+    /// Whether the value lies within this range, the upper bound is exclusive
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value < self.upper
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceDimmable {
     pub value: f64,
@@ -116,7 +126,7 @@ pub struct DeviceDimmable {
     pub range: DimmableRange,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DeviceSensor {
     pub label: String,
@@ -135,11 +145,72 @@ pub struct DeviceSensor {
 //     pub watts: u16,
 // }
 
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDeviceRequest {
+    #[serde(rename = "type")]
+    pub type_: DeviceType,
+    pub id: String,
+    pub name: String,
+    pub room_id: String,
+    pub vendor_id: String,
+    pub model_id: String,
+}
+
+#[derive(Serialize)]
+struct DeleteDeviceRequest<'request> {
+    id: &'request str,
+}
+
+/// Describes why a device action was rejected before it was sent to the server
+#[derive(Debug)]
+pub enum DeviceValidationError {
+    /// The device's driver does not implement the capability required by the action
+    MissingCapability {
+        device_id: String,
+        capability: DeviceCapability,
+    },
+    /// The device does not provide a dimmable with the given label
+    UnknownDimmable { device_id: String, label: String },
+    /// The value lies outside of the dimmable's range
+    DimValueOutOfRange {
+        label: String,
+        value: f64,
+        range: DimmableRange,
+    },
+}
+
+impl Display for DeviceValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceValidationError::MissingCapability {
+                device_id,
+                capability,
+            } => write!(
+                f,
+                "device `{device_id}` does not support the capability `{capability:?}`"
+            ),
+            DeviceValidationError::UnknownDimmable { device_id, label } => {
+                write!(f, "device `{device_id}` has no dimmable labeled `{label}`")
+            }
+            DeviceValidationError::DimValueOutOfRange {
+                label,
+                value,
+                range,
+            } => write!(
+                f,
+                "value {value} of dimmable `{label}` is outside of the range [{}, {})",
+                range.lower, range.upper
+            ),
+        }
+    }
+}
+
 //
 // END DEVICE.
 //
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct PowerDrawPoint {
     pub id: u64,
     pub time: u64,
@@ -147,7 +218,7 @@ pub struct PowerDrawPoint {
     pub off: PowerDrawData,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct PowerDrawData {
     #[serde(rename = "switchCount")]
     pub switch_count: usize,
@@ -163,7 +234,7 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.set_power(
     ///             "test-switch",
@@ -172,21 +243,265 @@ impl Client {
     /// }
     /// ```
     pub async fn set_power(&self, switch: &str, power_on: bool) -> Result<()> {
-        let response = self
-            .client
-            .execute(self.build_request::<DeviceRequest>(
-                Method::POST,
+        self.call(
+            Endpoint::post(
                 "/api/devices/action/power",
-                Some(DeviceRequest {
+                DeviceRequest {
                     device_id: switch,
                     power: Some(DevicePowerRequest { state: power_on }),
-                }),
-            )?)
+                    dim: None,
+                    color: None,
+                },
+            )
+            .permission(Permission::SetPower),
+        )
+        .await
+    }
+
+    /// Sets the value of the given dimmable of a device
+    /// The value is validated against the dimmable's range and the device's capabilities before
+    /// the request is sent
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let device = client.set_dim(
+    ///             "test-lamp",
+    ///             "brightness",
+    ///             50.0,
+    ///     ).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_dim(
+        &self,
+        device_id: &str,
+        label: &str,
+        value: f64,
+    ) -> Result<HydratedDeviceResponse> {
+        let device = self
+            .validated_device(device_id, DeviceCapability::Dimmable)
             .await?;
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(()),
-            status => Err(Error::Smarthome(status)),
+
+        let dimmable = device
+            .extractions
+            .dimmables
+            .iter()
+            .flatten()
+            .find(|dimmable| dimmable.label == label)
+            .ok_or_else(|| {
+                Error::DeviceValidation(DeviceValidationError::UnknownDimmable {
+                    device_id: device_id.to_string(),
+                    label: label.to_string(),
+                })
+            })?;
+
+        if !dimmable.range.contains(value) {
+            return Err(Error::DeviceValidation(
+                DeviceValidationError::DimValueOutOfRange {
+                    label: label.to_string(),
+                    value,
+                    range: dimmable.range.clone(),
+                },
+            ));
         }
+
+        self.device_action(
+            "/api/devices/action/dim",
+            DeviceRequest {
+                device_id,
+                power: None,
+                dim: Some(DeviceDimRequest { label, value }),
+                color: None,
+            },
+        )
+        .await
+    }
+
+    /// Sets the color of the given device
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, DeviceColor};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let device = client.set_color(
+    ///             "test-lamp",
+    ///             DeviceColor { r: 255, g: 128, b: 0 },
+    ///     ).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_color(
+        &self,
+        device_id: &str,
+        color: DeviceColor,
+    ) -> Result<HydratedDeviceResponse> {
+        self.validated_device(device_id, DeviceCapability::Color)
+            .await?;
+
+        self.device_action(
+            "/api/devices/action/color",
+            DeviceRequest {
+                device_id,
+                power: None,
+                dim: None,
+                color: Some(color),
+            },
+        )
+        .await
+    }
+
+    /// Reads the current values of the given device's sensors
+    /// The values are contained in the `sensors` field of the returned device's extractions
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let device = client.read_sensors("test-thermometer").await.unwrap();
+    /// }
+    /// ```
+    pub async fn read_sensors(&self, device_id: &str) -> Result<HydratedDeviceResponse> {
+        self.validated_device(device_id, DeviceCapability::Sensor)
+            .await?;
+
+        self.device_action(
+            "/api/devices/action/sensors",
+            DeviceRequest {
+                device_id,
+                power: None,
+                dim: None,
+                color: None,
+            },
+        )
+        .await
+    }
+
+    /// Fetches the given device and checks that it implements the required capability
+    async fn validated_device(
+        &self,
+        device_id: &str,
+        capability: DeviceCapability,
+    ) -> Result<HydratedDeviceResponse> {
+        let device = self.get_device(device_id).await?;
+
+        if !device.extractions.config.capabilities.contains(&capability) {
+            return Err(Error::DeviceValidation(
+                DeviceValidationError::MissingCapability {
+                    device_id: device_id.to_string(),
+                    capability,
+                },
+            ));
+        }
+
+        Ok(device)
+    }
+
+    async fn device_action(
+        &self,
+        path: &str,
+        request: DeviceRequest<'_>,
+    ) -> Result<HydratedDeviceResponse> {
+        self.call(
+            Endpoint::post(path, request)
+                .json::<HydratedDeviceResponse>()
+                .permission(Permission::SetPower),
+        )
+        .await
+    }
+
+    /// Returns a single device, including its extractions
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let device = client.get_device("test-switch").await.unwrap();
+    /// }
+    /// ```
+    pub async fn get_device(&self, device_id: &str) -> Result<HydratedDeviceResponse> {
+        self.call(
+            Endpoint::get(format!("/api/devices/get/{device_id}")).json::<HydratedDeviceResponse>(),
+        )
+        .await
+    }
+
+    /// Creates a new device on the target system
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, CreateDeviceRequest, DeviceType};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.create_device(&CreateDeviceRequest {
+    ///         type_: DeviceType::Output,
+    ///         id: "desk_lamp".to_string(),
+    ///         name: "Desk Lamp".to_string(),
+    ///         room_id: "office".to_string(),
+    ///         vendor_id: "smarthome".to_string(),
+    ///         model_id: "relay".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_device(&self, request: &CreateDeviceRequest) -> Result<()> {
+        self.call(
+            Endpoint::post("/api/devices/configure/add", request)
+                .permission(Permission::ModifyRooms),
+        )
+        .await
+    }
+
+    /// Modifies an existing device, the device is identified by its `id`
+    /// This allows renaming the device, moving it to another room, changing its driver or
+    /// updating its `singleton_json`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let mut device = client.get_device("desk_lamp").await.unwrap().shallow;
+    ///     device.room_id = "bedroom".to_string();
+    ///     client.modify_device(&device).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_device(&self, device: &ShallowDeviceResponse) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/devices/configure/modify", device)
+                .permission(Permission::ModifyRooms),
+        )
+        .await
+    }
+
+    /// Deletes a device from the target system
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_device("desk_lamp").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_device(&self, device_id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete(
+                "/api/devices/configure/delete",
+                DeleteDeviceRequest { id: device_id },
+            )
+            .permission(Permission::ModifyRooms),
+        )
+        .await
     }
 
     /// Returns the personal switches of the current user
@@ -195,24 +510,16 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.personal_switches().await.unwrap();
     /// }
     /// ```
     pub async fn personal_switches(&self) -> Result<Vec<HydratedDeviceResponse>> {
-        let response = self
-            .client
-            .execute(self.build_request::<()>(
-                Method::GET,
-                "/api/devices/list/personal/rich",
-                None,
-            )?)
-            .await?;
-        match response.status() {
-            StatusCode::OK => Ok(response.json::<Vec<HydratedDeviceResponse>>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::get("/api/devices/list/personal/rich").json::<Vec<HydratedDeviceResponse>>(),
+        )
+        .await
     }
 
     /// Returns all switches of the target system
@@ -221,20 +528,14 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///
     ///     let res = client.all_switches().await.unwrap();
     /// }
     /// ```
     pub async fn all_switches(&self) -> Result<Vec<HydratedDeviceResponse>> {
-        let response = self
-            .client
-            .execute(self.build_request::<()>(Method::GET, "/api/devices/list/all/rich", None)?)
-            .await?;
-        match response.status() {
-            StatusCode::OK => Ok(response.json::<Vec<HydratedDeviceResponse>>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(Endpoint::get("/api/devices/list/all/rich").json::<Vec<HydratedDeviceResponse>>())
+            .await
     }
 
     /// Returns power usage data from the server
@@ -244,27 +545,21 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///     // Will only fetch data from the last 24 hours
     ///     let res = client.power_usage(false).await.unwrap();
     /// }
     /// ```
     pub async fn power_usage(&self, fetch_all: bool) -> Result<Vec<PowerDrawPoint>> {
-        let response = self
-            .client
-            .execute(self.build_request::<()>(
-                Method::GET,
-                if fetch_all {
-                    "/api/power/usage/all"
-                } else {
-                    "/api/power/usage/day"
-                },
-                None,
-            )?)
-            .await?;
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(response.json::<Vec<PowerDrawPoint>>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(
+            Endpoint::get(if fetch_all {
+                "/api/power/usage/all"
+            } else {
+                "/api/power/usage/day"
+            })
+            .json::<Vec<PowerDrawPoint>>()
+            .permission(Permission::PowerUsage),
+        )
+        .await
     }
 }
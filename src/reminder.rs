@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Reminder {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub priority: ReminderPriority,
+    /// Unix timestamp in milliseconds
+    pub created_date: u64,
+    /// Unix timestamp in milliseconds
+    pub due_date: u64,
+    pub owner: String,
+    /// Whether the owner has already been notified about the upcoming due date
+    pub user_was_notified: bool,
+    /// Unix timestamp in milliseconds, only meaningful if `user_was_notified` is set
+    pub user_was_notified_at: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderData {
+    pub name: String,
+    pub description: String,
+    pub priority: ReminderPriority,
+    /// Unix timestamp in milliseconds
+    pub due_date: u64,
+}
+
+/// The priority of a reminder, encoded as a number by the server
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum ReminderPriority {
+    Low,
+    Normal,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<ReminderPriority> for u8 {
+    fn from(priority: ReminderPriority) -> Self {
+        match priority {
+            ReminderPriority::Low => 0,
+            ReminderPriority::Normal => 1,
+            ReminderPriority::Medium => 2,
+            ReminderPriority::High => 3,
+            ReminderPriority::Urgent => 4,
+        }
+    }
+}
+
+impl TryFrom<u8> for ReminderPriority {
+    type Error = String;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ReminderPriority::Low),
+            1 => Ok(ReminderPriority::Normal),
+            2 => Ok(ReminderPriority::Medium),
+            3 => Ok(ReminderPriority::High),
+            4 => Ok(ReminderPriority::Urgent),
+            other => Err(format!("invalid reminder priority `{other}`")),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ModifyReminderRequest<'request> {
+    id: u64,
+    data: &'request ReminderData,
+}
+
+#[derive(Serialize)]
+struct ReminderIdRequest {
+    id: u64,
+}
+
+impl Client {
+    /// Returns the reminders of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let reminders = client.list_reminders().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_reminders(&self) -> Result<Vec<Reminder>> {
+        self.call(
+            Endpoint::get("/api/reminder/list")
+                .json::<Vec<Reminder>>()
+                .permission(Permission::Reminder),
+        )
+        .await
+    }
+
+    /// Adds a new reminder for the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, ReminderData, ReminderPriority};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.add_reminder(&ReminderData {
+    ///         name: "Water the plants".to_string(),
+    ///         description: "Including the ones on the balcony".to_string(),
+    ///         priority: ReminderPriority::Normal,
+    ///         due_date: 1700000000000,
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn add_reminder(&self, data: &ReminderData) -> Result<()> {
+        self.call(Endpoint::post("/api/reminder/add", data).permission(Permission::Reminder))
+            .await
+    }
+
+    /// Replaces the data of an existing reminder
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, ReminderData, ReminderPriority};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.modify_reminder(42, &ReminderData {
+    ///         name: "Water the plants".to_string(),
+    ///         description: "Including the ones on the balcony".to_string(),
+    ///         priority: ReminderPriority::Urgent,
+    ///         due_date: 1700000000000,
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_reminder(&self, id: u64, data: &ReminderData) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/reminder/modify", ModifyReminderRequest { id, data })
+                .permission(Permission::Reminder),
+        )
+        .await
+    }
+
+    /// Deletes a reminder without marking it as done
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_reminder(42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_reminder(&self, id: u64) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/reminder/delete", ReminderIdRequest { id })
+                .permission(Permission::Reminder),
+        )
+        .await
+    }
+
+    /// Marks a reminder as done
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.mark_reminder_done(42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn mark_reminder_done(&self, id: u64) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/reminder/done", ReminderIdRequest { id })
+                .permission(Permission::Reminder),
+        )
+        .await
+    }
+}
@@ -1,9 +1,13 @@
-use reqwest::Request;
+use reqwest::cookie::CookieStore;
+use reqwest::header::{HeaderValue, COOKIE};
+use reqwest::{Request, Response, StatusCode};
 use serde::Serialize;
 
-use crate::errors::Result;
+use crate::cassette::{self, Cassette};
+use crate::errors::{Error, Result};
 use crate::Auth;
 use crate::Client;
+use crate::RetryPolicy;
 
 impl Client {
     /// Wrapper around `reqwest` which automatically handles authentication and body attachment
@@ -13,8 +17,9 @@ impl Client {
         path: &str,
         body: Option<T>,
     ) -> Result<Request> {
+        let url = self.smarthome_url.join(path)?;
         // Create a request
-        let mut request = self.client.request(method, self.smarthome_url.join(path)?);
+        let mut request = self.client.request(method, url.clone());
         // Depending on the authentication mode, choose a query-type
         request = match &self.auth {
             Auth::None => request,
@@ -22,6 +27,11 @@ impl Client {
                 request.query(&[("username", &user.username), ("password", &user.password)])
             }
             Auth::QueryToken(token) => request.query(&[("token", token)]),
+            // Session authentication only relies on the cookie obtained during login
+            Auth::Session(_) => match self.session_cookie(&url) {
+                Some(cookie) => request.header(COOKIE, cookie),
+                None => request,
+            },
         };
         // Append a body if needed
         match body {
@@ -29,4 +39,95 @@ impl Client {
             None => Ok(request.build()?),
         }
     }
+
+    /// Executes a request which was created using `build_request`
+    /// Temporary failures are retried according to the client's `RetryPolicy`
+    /// If the server rejects an expired session, the client logs in again and repeats the request once
+    pub async fn execute(&self, request: Request) -> Result<Response> {
+        self.execute_using(&self.client, request).await
+    }
+
+    /// Executes a request like `execute`, but sends it using the given `reqwest` client
+    pub(crate) async fn execute_using(
+        &self,
+        http: &reqwest::Client,
+        request: Request,
+    ) -> Result<Response> {
+        let retry = match &self.auth {
+            Auth::Session(_) => request.try_clone(),
+            _ => None,
+        };
+
+        let response = self.send_with_retries(http, request).await?;
+
+        match (response.status(), retry) {
+            (StatusCode::UNAUTHORIZED, Some(mut retry)) => {
+                self.login_with_credentials().await?;
+                // Replace the stale cookie with the one of the new session
+                match self.session_cookie(retry.url()) {
+                    Some(cookie) => retry.headers_mut().insert(COOKIE, cookie),
+                    None => retry.headers_mut().remove(COOKIE),
+                };
+                self.send_with_retries(http, retry).await
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Sends a request and repeats it while it fails temporarily and the retry policy allows it
+    async fn send_with_retries(
+        &self,
+        http: &reqwest::Client,
+        request: Request,
+    ) -> Result<Response> {
+        send_with_retries(
+            http,
+            self.cassette.as_ref(),
+            &self.retry_policy,
+            request,
+            false,
+        )
+        .await
+    }
+
+    fn session_cookie(&self, url: &reqwest::Url) -> Option<HeaderValue> {
+        self.cookie_jar.cookies(url)
+    }
+}
+
+/// Sends a request and repeats it while it fails temporarily and the retry policy allows it
+/// Requests are only repeated if their method is idempotent, unless `idempotent` marks them as such
+/// This is used before the client has been created, for instance for the version check
+pub(crate) async fn send_with_retries(
+    client: &reqwest::Client,
+    cassette: Option<&Cassette>,
+    policy: &RetryPolicy,
+    mut request: Request,
+    idempotent: bool,
+) -> Result<Response> {
+    let allowed = idempotent || policy.allows(request.method());
+    let mut attempt = 1;
+    loop {
+        let next = match allowed && attempt < policy.max_attempts {
+            true => request.try_clone(),
+            false => None,
+        };
+        let Some(next) = next else {
+            return cassette::send(client, cassette, request).await;
+        };
+
+        let result = cassette::send(client, cassette, request).await;
+        let retryable = match &result {
+            Ok(response) => policy.retryable_statuses.contains(&response.status()),
+            Err(Error::Reqwest(err)) => policy.retry_connect_errors && err.is_connect(),
+            Err(_) => false,
+        };
+        if !retryable {
+            return result;
+        }
+
+        tokio::time::sleep(policy.backoff(attempt)).await;
+        request = next;
+        attempt += 1;
+    }
 }
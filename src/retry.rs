@@ -0,0 +1,163 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+
+/// Controls how requests which failed due to temporary issues are repeated
+/// Requests using non-idempotent methods, such as `POST`, are only repeated if `retry_non_idempotent` is set
+/// Clients use the default policy unless configured otherwise, which sends a request up to 3 times
+/// ```rust no_run
+/// use std::time::Duration;
+/// use smarthome_sdk_rs::{Auth, Client, RetryPolicy};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = Client::builder("https://smarthome.box")
+///         .auth(Auth::QueryToken("token".to_string()))
+///         .retry_policy(RetryPolicy {
+///             max_attempts: 5,
+///             max_backoff: Duration::from_secs(30),
+///             ..Default::default()
+///         })
+///         .build()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// How often a request is sent at most, including the first attempt
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled on every further retry
+    pub initial_backoff: Duration,
+    /// The upper limit of the delay between two attempts
+    pub max_backoff: Duration,
+    /// Randomizes each delay to between half and all of its value
+    /// This prevents many clients from retrying at the same time
+    pub jitter: bool,
+    /// Responses using these status codes are considered temporary failures
+    pub retryable_statuses: Vec<StatusCode>,
+    /// Whether requests are repeated if no connection to the server could be established
+    pub retry_connect_errors: bool,
+    /// Whether requests which are not idempotent, for instance running a Homescript, are repeated as well
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+            retryable_statuses: vec![
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_connect_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy which sends every request exactly once
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a request using the given method may be repeated
+    pub(crate) fn allows(&self, method: &Method) -> bool {
+        self.retry_non_idempotent
+            || matches!(
+                *method,
+                Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+            )
+    }
+
+    /// Returns the delay before the given retry, starting at `1`
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        match self.jitter {
+            true => {
+                // The std hasher is randomly seeded which is sufficient for spreading out retries
+                let random = RandomState::new().hash_one(retry) as f64 / u64::MAX as f64;
+                backoff.mul_f64(0.5 + random / 2.0)
+            }
+            false => backoff,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(jitter: bool) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            jitter,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_until_the_cap() {
+        let policy = policy(false);
+        let delays: Vec<u64> = (1..=6)
+            .map(|retry| policy.backoff(retry).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [100, 200, 400, 800, 1000, 1000]);
+        // Large retry counts must not overflow
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_all_of_the_backoff() {
+        let jittered = policy(true);
+        let plain = policy(false);
+        for retry in 1..=6 {
+            let backoff = plain.backoff(retry);
+            for _ in 0..100 {
+                let delay = jittered.backoff(retry);
+                assert!(
+                    delay >= backoff / 2 && delay <= backoff,
+                    "{delay:?} for {backoff:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn only_idempotent_methods_are_allowed_by_default() {
+        let policy = RetryPolicy::default();
+        for method in [
+            Method::GET,
+            Method::HEAD,
+            Method::OPTIONS,
+            Method::PUT,
+            Method::DELETE,
+        ] {
+            assert!(policy.allows(&method), "{method}");
+        }
+        for method in [Method::POST, Method::PATCH] {
+            assert!(!policy.allows(&method), "{method}");
+        }
+
+        let policy = RetryPolicy {
+            retry_non_idempotent: true,
+            ..Default::default()
+        };
+        assert!(policy.allows(&Method::POST));
+    }
+}
@@ -1,22 +1,23 @@
-use crate::{errors::Result, Auth, Client, Error};
+use crate::{endpoint::Endpoint, errors::Result, Client, HydratedDeviceResponse, Permission};
 use bytes::Bytes;
-use reqwest::{Method, StatusCode};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Room {
     pub data: RoomData,
-    pub switches: Vec<Switch>,
+    #[serde(alias = "devices")]
+    pub switches: Vec<HydratedDeviceResponse>,
     pub cameras: Vec<Camera>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct RoomData {
     pub id: String,
     pub name: String,
     pub description: String,
 }
 
+/// The legacy device model, rooms now contain `HydratedDeviceResponse`s instead
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Switch {
@@ -27,7 +28,7 @@ pub struct Switch {
     pub watts: u16,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Camera {
     pub id: String,
@@ -36,6 +37,11 @@ pub struct Camera {
     pub room_id: String,
 }
 
+#[derive(Serialize)]
+struct DeleteRequest<'request> {
+    id: &'request str,
+}
+
 impl Client {
     /// Returns a list containing the personal rooms of the current user
     /// ```rust no_run
@@ -43,41 +49,160 @@ impl Client {
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let client = Client::new("foo", Auth::None).await.unwrap();
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
     ///     let res = client.personal_rooms().await.unwrap();
     /// }
     /// ```
     pub async fn personal_rooms(&self) -> Result<Vec<Room>> {
-        let response = self
-            .client
-            .execute(self.build_request::<()>(Method::GET, "/api/room/list/personal", None)?)
-            .await?;
-        match response.status() {
-            reqwest::StatusCode::OK => Ok(response.json::<Vec<Room>>().await?),
-            status => Err(Error::Smarthome(status)),
-        }
+        self.call(Endpoint::get("/api/room/list/personal").json::<Vec<Room>>())
+            .await
+    }
+
+    /// Returns a list containing all rooms of the target system
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     let res = client.list_all_rooms().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_all_rooms(&self) -> Result<Vec<Room>> {
+        self.call(Endpoint::get("/api/room/list/all").json::<Vec<Room>>())
+            .await
+    }
+
+    /// Creates a new room on the target system
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, RoomData};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.create_room(&RoomData {
+    ///         id: "office".to_string(),
+    ///         name: "Office".to_string(),
+    ///         description: "The home office".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_room(&self, data: &RoomData) -> Result<()> {
+        self.call(Endpoint::post("/api/room/add", data).permission(Permission::ModifyRooms))
+            .await
+    }
+
+    /// Modifies the name and description of a room, the room is identified by its `id`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, RoomData};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.modify_room(&RoomData {
+    ///         id: "office".to_string(),
+    ///         name: "Study".to_string(),
+    ///         description: "The home office".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_room(&self, data: &RoomData) -> Result<()> {
+        self.call(Endpoint::put("/api/room/modify", data).permission(Permission::ModifyRooms))
+            .await
     }
 
+    /// Deletes a room, including its devices and cameras
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.delete_room("office").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_room(&self, id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/room/delete", &DeleteRequest { id })
+                .permission(Permission::ModifyRooms),
+        )
+        .await
+    }
+
+    /// Adds a camera to the room specified by its `room_id`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Camera};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.add_camera(&Camera {
+    ///         id: "office_cam".to_string(),
+    ///         name: "Office Camera".to_string(),
+    ///         url: "http://camera.box/snapshot.jpg".to_string(),
+    ///         room_id: "office".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn add_camera(&self, camera: &Camera) -> Result<()> {
+        self.call(Endpoint::post("/api/camera/add", camera).permission(Permission::ModifyRooms))
+            .await
+    }
+
+    /// Modifies a camera, the camera is identified by its `id`
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, Camera};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.modify_camera(&Camera {
+    ///         id: "office_cam".to_string(),
+    ///         name: "Office Camera".to_string(),
+    ///         url: "http://camera.box/image.jpg".to_string(),
+    ///         room_id: "office".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_camera(&self, camera: &Camera) -> Result<()> {
+        self.call(Endpoint::put("/api/camera/modify", camera).permission(Permission::ModifyRooms))
+            .await
+    }
+
+    /// Deletes a camera from the target system
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     client.delete_camera("office_cam").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_camera(&self, id: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/camera/delete", &DeleteRequest { id })
+                .permission(Permission::ModifyRooms),
+        )
+        .await
+    }
+
+    /// Fetches the current image of the given camera
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///     let image = client.camera_feed("test-camera").await.unwrap();
+    /// }
+    /// ```
     pub async fn camera_feed(&self, camera_id: &str) -> Result<Bytes> {
-        // Create a base request
-        let request = self.client.get(
-            self.smarthome_url
-                .join(&format!("/api/camera/feed/{camera_id}"))?,
-        );
-        // Depending on the authentication mode, choose a query-type
-        let response = match &self.auth {
-            Auth::None => request,
-            Auth::QueryPassword(user) => {
-                request.query(&[("username", &user.username), ("password", &user.password)])
-            }
-            Auth::QueryToken(token) => request.query(&[("token", token)]),
-        }
-        .send()
-        .await?;
-        // Check the status code and return the corresponding result
-        match response.status() {
-            StatusCode::OK => Ok(response.bytes().await?),
-            code => Err(Error::Smarthome(code)),
-        }
+        self.call(
+            Endpoint::get(format!("/api/camera/feed/{camera_id}"))
+                .binary()
+                .permission(Permission::ViewCameras),
+        )
+        .await
     }
 }
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+/// A one-shot job which is executed once at the given time and removed afterwards
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Schedule {
+    pub id: u64,
+    pub owner: String,
+    pub data: ScheduleData,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleData {
+    pub name: String,
+    pub hour: u8,
+    pub minute: u8,
+    pub enabled: bool,
+    pub target: ScheduleTarget,
+}
+
+/// Specifies what is executed when a schedule is due
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(
+    tag = "mode",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ScheduleTarget {
+    /// Runs an existing Homescript
+    Homescript { homescript_id: String },
+    /// Runs the given Homescript code
+    Code { code: String },
+    /// Sets the power state of the given devices
+    Devices { actions: Vec<SchedulePowerAction> },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePowerAction {
+    pub device_id: String,
+    pub power: bool,
+}
+
+#[derive(Serialize)]
+struct ModifyScheduleRequest<'request> {
+    id: u64,
+    data: &'request ScheduleData,
+}
+
+#[derive(Serialize)]
+struct DeleteScheduleRequest {
+    id: u64,
+}
+
+impl Client {
+    /// Returns the personal schedules of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let schedules = client.list_schedules().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_schedules(&self) -> Result<Vec<Schedule>> {
+        self.call(
+            Endpoint::get("/api/scheduler/list/personal")
+                .json::<Vec<Schedule>>()
+                .permission(Permission::Scheduler),
+        )
+        .await
+    }
+
+    /// Creates a new schedule
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, ScheduleData, SchedulePowerAction, ScheduleTarget};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.create_schedule(&ScheduleData {
+    ///         name: "Wake up".to_string(),
+    ///         hour: 6,
+    ///         minute: 30,
+    ///         enabled: true,
+    ///         target: ScheduleTarget::Devices {
+    ///             actions: vec![SchedulePowerAction {
+    ///                 device_id: "bedroom_lamp".to_string(),
+    ///                 power: true,
+    ///             }],
+    ///         },
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_schedule(&self, data: &ScheduleData) -> Result<()> {
+        self.call(Endpoint::post("/api/scheduler/add", data).permission(Permission::Scheduler))
+            .await
+    }
+
+    /// Replaces the data of an existing schedule
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let mut schedule = client.list_schedules().await.unwrap().remove(0);
+    ///     schedule.data.hour = 7;
+    ///     client.modify_schedule(schedule.id, &schedule.data).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_schedule(&self, id: u64, data: &ScheduleData) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/scheduler/modify", ModifyScheduleRequest { id, data })
+                .permission(Permission::Scheduler),
+        )
+        .await
+    }
+
+    /// Deletes a schedule before it is executed
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_schedule(42).await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_schedule(&self, id: u64) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/scheduler/delete", DeleteScheduleRequest { id })
+                .permission(Permission::Scheduler),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Asserts that the target is serialized to the given JSON and parsed back unchanged
+    fn assert_wire_format(target: ScheduleTarget, expected: Value) {
+        assert_eq!(serde_json::to_value(&target).unwrap(), expected);
+        assert_eq!(
+            serde_json::from_value::<ScheduleTarget>(expected).unwrap(),
+            target
+        );
+    }
+
+    #[test]
+    fn homescript() {
+        assert_wire_format(
+            ScheduleTarget::Homescript {
+                homescript_id: "morning".to_string(),
+            },
+            json!({ "mode": "homescript", "homescriptId": "morning" }),
+        );
+    }
+
+    #[test]
+    fn code() {
+        assert_wire_format(
+            ScheduleTarget::Code {
+                code: "println('hello')".to_string(),
+            },
+            json!({ "mode": "code", "code": "println('hello')" }),
+        );
+    }
+
+    #[test]
+    fn devices() {
+        assert_wire_format(
+            ScheduleTarget::Devices {
+                actions: vec![SchedulePowerAction {
+                    device_id: "lamp".to_string(),
+                    power: true,
+                }],
+            },
+            json!({ "mode": "devices", "actions": [{ "deviceId": "lamp", "power": true }] }),
+        );
+    }
+}
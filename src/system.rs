@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemConfig {
+    /// Whether automations are executed, toggled using `Client::set_automations_enabled`
+    pub automation_enabled: bool,
+    /// While the lockdown mode is active, only administrators can use the system
+    pub lockdown_mode: bool,
+    pub latitude: f64,
+    pub longitude: f64,
+    #[serde(rename = "openWeatherMapApiKey")]
+    pub open_weather_map_key: String,
+    pub mqtt: MqttConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize)]
+struct LocationRequest {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Serialize)]
+struct LockdownRequest {
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+struct OpenWeatherMapKeyRequest<'request> {
+    key: &'request str,
+}
+
+impl Client {
+    /// Returns the configuration of the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let config = client.get_system_config().await.unwrap();
+    /// }
+    /// ```
+    pub async fn get_system_config(&self) -> Result<SystemConfig> {
+        self.call(
+            Endpoint::get("/api/system/config")
+                .json::<SystemConfig>()
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Sets the location of the server, which is used for weather data and sun times
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.set_location(52.52, 13.405).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_location(&self, latitude: f64, longitude: f64) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/config/location",
+                LocationRequest {
+                    latitude,
+                    longitude,
+                },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Enables or disables the lockdown mode
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.set_lockdown_mode(true).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_lockdown_mode(&self, enabled: bool) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/system/config/lockdown", LockdownRequest { enabled })
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Sets the API key which is used to fetch weather data from OpenWeatherMap
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.set_open_weather_map_key("f0e1d2c3b4a5").await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_open_weather_map_key(&self, key: &str) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/system/config/openweathermap",
+                OpenWeatherMapKeyRequest { key },
+            )
+            .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+
+    /// Replaces the MQTT configuration of the target system
+    /// Requires the system-config permission.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, MqttConfig};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.set_mqtt_config(&MqttConfig {
+    ///         enabled: true,
+    ///         host: "mqtt.box".to_string(),
+    ///         port: 1883,
+    ///         username: "smarthome".to_string(),
+    ///         password: "password".to_string(),
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_mqtt_config(&self, config: &MqttConfig) -> Result<()> {
+        self.call(
+            Endpoint::put("/api/system/config/mqtt", config)
+                .permission(Permission::ModifyServerConfig),
+        )
+        .await
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{auth::Token, endpoint::Endpoint, errors::Result, Client};
+
+/// A long-lived token which can be used for authentication using `Auth::QueryToken`
+#[derive(Deserialize, Debug, Clone)]
+pub struct AuthToken {
+    pub token: String,
+    pub label: String,
+}
+
+#[derive(Deserialize)]
+struct CreateTokenResponse {
+    token: String,
+}
+
+#[derive(Serialize)]
+struct CreateTokenRequest<'request> {
+    label: &'request str,
+}
+
+impl Client {
+    /// Creates a new authentication token for the current user and returns it
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, User};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::Session(User {
+    ///         username: "ci".to_string(),
+    ///         password: "password".to_string(),
+    ///     }), true).await.unwrap();
+    ///
+    ///     let token = client.create_token("backup-service").await.unwrap();
+    ///     let service_client = Client::new("foo", Auth::QueryToken(token), true).await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_token(&self, label: &str) -> Result<String> {
+        Ok(self
+            .call(
+                Endpoint::post("/api/user/token/add", CreateTokenRequest { label })
+                    .json::<CreateTokenResponse>(),
+            )
+            .await?
+            .token)
+    }
+
+    /// Returns the authentication tokens of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let tokens = client.list_tokens().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_tokens(&self) -> Result<Vec<AuthToken>> {
+        self.call(Endpoint::get("/api/user/token/list/personal").json::<Vec<AuthToken>>())
+            .await
+    }
+
+    /// Revokes an authentication token, clients using it are logged out
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.revoke_token("b67f2f5c7f2e6795d9f9b55678db7579").await.unwrap();
+    /// }
+    /// ```
+    pub async fn revoke_token(&self, token: &str) -> Result<()> {
+        self.call(Endpoint::delete(
+            "/api/user/token/delete",
+            Token {
+                token: token.to_string(),
+            },
+        ))
+        .await
+    }
+}
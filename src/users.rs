@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{endpoint::Endpoint, errors::Result, Client, Permission};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UserData {
+    pub username: String,
+    #[serde(flatten)]
+    pub metadata: UserMetadata,
+}
+
+/// The modifiable data of a user
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMetadata {
+    pub forename: String,
+    pub surname: String,
+    /// Hex color which is used when the dark theme is enabled
+    pub primary_color_dark: String,
+    /// Hex color which is used when the light theme is enabled
+    pub primary_color_light: String,
+    pub dark_theme: bool,
+    pub scheduler_enabled: bool,
+}
+
+#[derive(Serialize)]
+struct CreateUserRequest<'request> {
+    username: &'request str,
+    password: &'request str,
+}
+
+#[derive(Serialize)]
+struct DeleteUserRequest<'request> {
+    username: &'request str,
+}
+
+#[derive(Serialize)]
+struct ModifyUserMetadataRequest<'request> {
+    username: &'request str,
+    data: &'request UserMetadata,
+}
+
+#[derive(Serialize)]
+struct ChangePasswordRequest<'request> {
+    password: &'request str,
+}
+
+impl Client {
+    /// Returns all users of the target system
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let users = client.list_users().await.unwrap();
+    /// }
+    /// ```
+    pub async fn list_users(&self) -> Result<Vec<UserData>> {
+        self.call(
+            Endpoint::get("/api/user/manage/list")
+                .json::<Vec<UserData>>()
+                .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Creates a new user
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.create_user("alice", "password").await.unwrap();
+    /// }
+    /// ```
+    pub async fn create_user(&self, username: &str, password: &str) -> Result<()> {
+        self.call(
+            Endpoint::post(
+                "/api/user/manage/add",
+                CreateUserRequest { username, password },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Deletes a user, including all of the user's data
+    /// Requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.delete_user("alice").await.unwrap();
+    /// }
+    /// ```
+    pub async fn delete_user(&self, username: &str) -> Result<()> {
+        self.call(
+            Endpoint::delete("/api/user/manage/delete", DeleteUserRequest { username })
+                .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Replaces the metadata of the given user
+    /// Modifying other users requires the permission to manage users.
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth, UserMetadata};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.modify_user_metadata("alice", &UserMetadata {
+    ///         forename: "Alice".to_string(),
+    ///         surname: "Doe".to_string(),
+    ///         primary_color_dark: "#88FF70".to_string(),
+    ///         primary_color_light: "#2E7D32".to_string(),
+    ///         dark_theme: true,
+    ///         scheduler_enabled: true,
+    ///     }).await.unwrap();
+    /// }
+    /// ```
+    pub async fn modify_user_metadata(&self, username: &str, data: &UserMetadata) -> Result<()> {
+        self.call(
+            Endpoint::put(
+                "/api/user/manage/data/modify",
+                ModifyUserMetadataRequest { username, data },
+            )
+            .permission(Permission::ModifyUsers),
+        )
+        .await
+    }
+
+    /// Changes the password of the current user
+    /// When using password-based authentication, a new client has to be created afterwards
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     client.change_password("new-password").await.unwrap();
+    /// }
+    /// ```
+    pub async fn change_password(&self, new_password: &str) -> Result<()> {
+        self.call(Endpoint::put(
+            "/api/user/password/modify",
+            ChangePasswordRequest {
+                password: new_password,
+            },
+        ))
+        .await
+    }
+
+    /// Returns the data of the current user
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let user = client.get_current_user_data().await.unwrap();
+    /// }
+    /// ```
+    pub async fn get_current_user_data(&self) -> Result<UserData> {
+        self.call(Endpoint::get("/api/user/data").json::<UserData>())
+            .await
+    }
+}
@@ -0,0 +1,84 @@
+use serde::Deserialize;
+
+use crate::{endpoint::Endpoint, errors::Result, Client};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherMeasurement {
+    pub id: u64,
+    /// Unix timestamp in milliseconds
+    pub time: u64,
+    pub weather_title: String,
+    pub weather_description: String,
+    /// Temperature in degrees Celsius
+    pub temperature: f64,
+    /// Perceived temperature in degrees Celsius
+    pub feels_like: f64,
+    /// Relative humidity in percent
+    pub humidity: u8,
+}
+
+/// The times of today's sunrise and sunset at the server's configured location
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct SunTimes {
+    pub sunrise: TimeOfDay,
+    pub sunset: TimeOfDay,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl Client {
+    /// Returns the current weather at the server's location
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let weather = client.current_weather().await.unwrap();
+    ///     println!("{} °C", weather.temperature);
+    /// }
+    /// ```
+    pub async fn current_weather(&self) -> Result<WeatherMeasurement> {
+        self.call(Endpoint::get("/api/weather").json::<WeatherMeasurement>())
+            .await
+    }
+
+    /// Returns the weather measurements which are cached by the server
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let measurements = client.weather_cache().await.unwrap();
+    /// }
+    /// ```
+    pub async fn weather_cache(&self) -> Result<Vec<WeatherMeasurement>> {
+        self.call(Endpoint::get("/api/weather/cache").json::<Vec<WeatherMeasurement>>())
+            .await
+    }
+
+    /// Returns the sunrise and sunset times which are used by the server's automations
+    /// ```rust no_run
+    /// use smarthome_sdk_rs::{Client, Auth};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("foo", Auth::None, true).await.unwrap();
+    ///
+    ///     let sun = client.sun_times().await.unwrap();
+    ///     println!("sunrise at {:02}:{:02}", sun.sunrise.hour, sun.sunrise.minute);
+    /// }
+    /// ```
+    pub async fn sun_times(&self) -> Result<SunTimes> {
+        self.call(Endpoint::get("/api/weather/sun").json::<SunTimes>())
+            .await
+    }
+}
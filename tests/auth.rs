@@ -0,0 +1,76 @@
+mod common;
+
+use common::{count, session_auth};
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Client, Error};
+
+#[tokio::test]
+async fn session_requests_only_send_the_cookie() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("admin", "admin")
+            .with_room("hall", "Hall"),
+    )
+    .await;
+    let client = Client::new(server.url(), session_auth("admin"), true)
+        .await
+        .unwrap();
+    assert_eq!(client.username.as_deref(), Some("admin"));
+
+    client.list_all_rooms().await.unwrap();
+    let state = server.state();
+    let request = state.requests.last().unwrap();
+    assert_eq!(request.path, "/api/room/list/all");
+    assert_eq!(request.query, None);
+}
+
+#[tokio::test]
+async fn expired_session_is_renewed_transparently() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("admin", "admin")
+            .with_room("hall", "Hall"),
+    )
+    .await;
+    let client = Client::new(server.url(), session_auth("admin"), true)
+        .await
+        .unwrap();
+    assert_eq!(count(&server, "/api/login"), 1);
+
+    server.state().sessions.clear();
+    assert_eq!(client.list_all_rooms().await.unwrap().len(), 1);
+    assert_eq!(count(&server, "/api/login"), 2);
+    assert_eq!(count(&server, "/api/room/list/all"), 2);
+
+    // The new session is used from now on
+    client.list_all_rooms().await.unwrap();
+    assert_eq!(count(&server, "/api/login"), 2);
+}
+
+#[tokio::test]
+async fn renewal_fails_if_the_credentials_were_revoked() {
+    let server = MockServer::start(MockState::default().with_user("admin", "admin")).await;
+    let client = Client::new(server.url(), session_auth("admin"), true)
+        .await
+        .unwrap();
+
+    {
+        let mut state = server.state();
+        state.sessions.clear();
+        state.users[0].password = "changed".to_string();
+    }
+    assert!(matches!(
+        client.list_all_rooms().await,
+        Err(Error::Unauthorized(_))
+    ));
+}
+
+#[tokio::test]
+async fn invalid_credentials_are_rejected() {
+    let server = MockServer::start(MockState::default().with_user("admin", "admin")).await;
+
+    assert!(matches!(
+        Client::new(server.url(), session_auth("wrong"), true).await,
+        Err(Error::Unauthorized(_))
+    ));
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Auth, CassetteError, Client, Error, User};
+
+/// Returns a cassette path which is unique to the test
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("smarthome-sdk-{name}-{}.jsonl", std::process::id()))
+}
+
+fn password_auth(password: &str) -> Auth {
+    Auth::QueryPassword(User {
+        username: "admin".to_string(),
+        password: password.to_string(),
+    })
+}
+
+#[tokio::test]
+async fn recorded_password_is_scrubbed_and_replayable() {
+    let path = cassette_path("password");
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("admin", "hunter2-password")
+            .with_device("lamp", "hall", false),
+    )
+    .await;
+
+    let client = Client::builder(server.url())
+        .auth(password_auth("hunter2-password"))
+        .record_cassette(&path)
+        .build()
+        .await
+        .unwrap();
+    client.set_power("lamp", true).await.unwrap();
+    drop(server);
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    assert!(!cassette.contains("hunter2-password"));
+    assert!(cassette.contains("[scrubbed]"));
+    assert!(cassette.contains("/api/devices/action/power"));
+    // Every interaction is stored on its own line: version, login and the power action
+    assert_eq!(cassette.lines().count(), 3);
+
+    // The server is gone, so every response has to come from the cassette
+    let client = Client::builder("http://127.0.0.1:9")
+        .auth(password_auth("another-password"))
+        .replay_cassette(&path)
+        .build()
+        .await
+        .unwrap();
+    client.set_power("lamp", true).await.unwrap();
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn recorded_token_is_scrubbed() {
+    let path = cassette_path("token");
+    let server = MockServer::start(MockState::default().with_user("admin", "admin").with_token(
+        "admin",
+        "t0ken-value",
+        "ci",
+    ))
+    .await;
+
+    let client = Client::builder(server.url())
+        .auth(Auth::QueryToken("t0ken-value".to_string()))
+        .record_cassette(&path)
+        .build()
+        .await
+        .unwrap();
+    client.list_all_rooms().await.unwrap();
+
+    let cassette = std::fs::read_to_string(&path).unwrap();
+    assert!(!cassette.contains("t0ken-value"));
+    assert!(cassette.contains("/api/room/list/all"));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn unmatched_replay_is_an_error() {
+    let path = cassette_path("unmatched");
+    let server = MockServer::start(MockState::default().with_room("hall", "Hall")).await;
+
+    let client = Client::builder(server.url())
+        .record_cassette(&path)
+        .build()
+        .await
+        .unwrap();
+    client.list_all_rooms().await.unwrap();
+    drop(server);
+
+    let client = Client::builder("http://127.0.0.1:9")
+        .replay_cassette(&path)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(client.list_all_rooms().await.unwrap().len(), 1);
+    // Every recorded interaction is only replayed once
+    match client.list_all_rooms().await {
+        Err(Error::Cassette(CassetteError::Unmatched { method, url })) => {
+            assert_eq!(method, "GET");
+            assert_eq!(url, "/api/room/list/all");
+        }
+        other => panic!("expected an unmatched request, got {other:?}"),
+    }
+    assert!(matches!(
+        client.personal_rooms().await,
+        Err(Error::Cassette(CassetteError::Unmatched { .. }))
+    ));
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the integration tests
+
+use smarthome_sdk_rs::mock::MockServer;
+use smarthome_sdk_rs::{Auth, User};
+
+/// Session authentication as the user `admin`
+pub fn session_auth(password: &str) -> Auth {
+    Auth::Session(User {
+        username: "admin".to_string(),
+        password: password.to_string(),
+    })
+}
+
+/// Returns how many requests to the given path reached the server
+pub fn count(server: &MockServer, path: &str) -> usize {
+    server
+        .state()
+        .requests
+        .iter()
+        .filter(|request| request.path == path)
+        .count()
+}
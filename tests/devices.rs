@@ -0,0 +1,286 @@
+use serde_json::json;
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{
+    Auth, Client, CreateDeviceRequest, DeviceCapability, DeviceColor, DeviceDimmable, DeviceSensor,
+    DeviceType, DeviceValidationError, DimmableRange, Error, PowerDrawData, PowerDrawPoint,
+};
+
+fn create_request(id: &str) -> CreateDeviceRequest {
+    CreateDeviceRequest {
+        type_: DeviceType::Output,
+        id: id.to_string(),
+        name: "Desk Lamp".to_string(),
+        room_id: "office".to_string(),
+        vendor_id: "acme".to_string(),
+        model_id: "relay".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn device_crud() {
+    let server = MockServer::start(MockState::default().with_room("office", "Office")).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.create_device(&create_request("desk")).await.unwrap();
+    let mut device = client.get_device("desk").await.unwrap();
+    assert_eq!(device.shallow.name, "Desk Lamp");
+    assert_eq!(client.all_switches().await.unwrap().len(), 1);
+
+    device.shallow.name = "Reading Lamp".to_string();
+    client.modify_device(&device.shallow).await.unwrap();
+    assert_eq!(
+        client.get_device("desk").await.unwrap().shallow.name,
+        "Reading Lamp"
+    );
+
+    client.delete_device("desk").await.unwrap();
+    assert!(client.all_switches().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn set_power_updates_the_device() {
+    let server = MockServer::start(MockState::default().with_device("lamp", "office", false)).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.set_power("lamp", true).await.unwrap();
+    let device = client.get_device("lamp").await.unwrap();
+    assert!(device.extractions.power_information.unwrap().state);
+}
+
+#[tokio::test]
+async fn missing_device_is_not_found() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let err = client.get_device("ghost").await.unwrap_err();
+    let Error::NotFound(response) = err else {
+        panic!("expected `NotFound`, got {err:?}");
+    };
+    assert_eq!(response.path, "/api/devices/get/ghost");
+    assert_eq!(response.body.unwrap().message, "device does not exist");
+
+    assert!(matches!(
+        client.delete_device("ghost").await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn duplicate_device_is_a_conflict() {
+    let server = MockServer::start(MockState::default().with_device("lamp", "office", false)).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    assert!(matches!(
+        client.create_device(&create_request("lamp")).await,
+        Err(Error::Conflict(_))
+    ));
+}
+
+#[tokio::test]
+async fn unsupported_action_is_a_validation_error() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    // Devices created through the API have no power information
+    client
+        .create_device(&create_request("sensor"))
+        .await
+        .unwrap();
+    let err = client.set_power("sensor", true).await.unwrap_err();
+    let Error::Validation(response) = err else {
+        panic!("expected `Validation`, got {err:?}");
+    };
+    assert_eq!(response.status, 422);
+}
+
+#[tokio::test]
+async fn power_usage() {
+    let point = PowerDrawPoint {
+        id: 1,
+        time: 1700000000000,
+        on: PowerDrawData {
+            switch_count: 2,
+            watts: 120,
+            percent: 100.0,
+        },
+        off: PowerDrawData {
+            switch_count: 0,
+            watts: 0,
+            percent: 0.0,
+        },
+    };
+    let server = MockServer::start(MockState {
+        power_usage: vec![point],
+        ..Default::default()
+    })
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let usage = client.power_usage(false).await.unwrap();
+    assert_eq!(usage[0].on.watts, 120);
+    assert_eq!(client.power_usage(true).await.unwrap().len(), 1);
+}
+
+fn brightness(value: f64) -> DeviceDimmable {
+    DeviceDimmable {
+        value,
+        label: "brightness".to_string(),
+        range: DimmableRange {
+            lower: 0.0,
+            upper: 100.0,
+        },
+    }
+}
+
+/// Whether any device action reached the server
+fn sent_action(server: &MockServer) -> bool {
+    server
+        .state()
+        .requests
+        .iter()
+        .any(|request| request.path.starts_with("/api/devices/action/"))
+}
+
+#[tokio::test]
+async fn set_dim_returns_the_updated_device() {
+    let server = MockServer::start(MockState::default().with_dimmable_device(
+        "lamp",
+        "office",
+        vec![brightness(10.0)],
+    ))
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let device = client.set_dim("lamp", "brightness", 42.5).await.unwrap();
+    assert_eq!(device.shallow.id, "lamp");
+    assert_eq!(device.extractions.dimmables.unwrap()[0].value, 42.5);
+    assert_eq!(
+        server
+            .state()
+            .device("lamp")
+            .unwrap()
+            .extractions
+            .dimmables
+            .as_ref()
+            .unwrap()[0]
+            .value,
+        42.5
+    );
+}
+
+#[tokio::test]
+async fn set_dim_rejects_values_outside_of_the_range() {
+    let server = MockServer::start(MockState::default().with_dimmable_device(
+        "lamp",
+        "office",
+        vec![brightness(10.0)],
+    ))
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    // The upper bound is exclusive
+    for value in [-1.0, 100.0] {
+        let err = client
+            .set_dim("lamp", "brightness", value)
+            .await
+            .unwrap_err();
+        let Error::DeviceValidation(DeviceValidationError::DimValueOutOfRange {
+            label,
+            value: rejected,
+            range,
+        }) = err
+        else {
+            panic!("expected `DimValueOutOfRange`, got {err:?}");
+        };
+        assert_eq!(label, "brightness");
+        assert_eq!(rejected, value);
+        assert_eq!(range.upper, 100.0);
+    }
+    assert!(!sent_action(&server));
+}
+
+#[tokio::test]
+async fn set_dim_rejects_unknown_labels() {
+    let server = MockServer::start(MockState::default().with_dimmable_device(
+        "lamp",
+        "office",
+        vec![brightness(10.0)],
+    ))
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let err = client.set_dim("lamp", "warmth", 50.0).await.unwrap_err();
+    let Error::DeviceValidation(DeviceValidationError::UnknownDimmable { device_id, label }) = err
+    else {
+        panic!("expected `UnknownDimmable`, got {err:?}");
+    };
+    assert_eq!(device_id, "lamp");
+    assert_eq!(label, "warmth");
+    assert!(!sent_action(&server));
+}
+
+#[tokio::test]
+async fn actions_require_the_capability() {
+    let server =
+        MockServer::start(MockState::default().with_device("relay", "office", false)).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let missing = |result| match result {
+        Err(Error::DeviceValidation(DeviceValidationError::MissingCapability {
+            device_id,
+            capability,
+        })) => {
+            assert_eq!(device_id, "relay");
+            capability
+        }
+        other => panic!("expected `MissingCapability`, got {other:?}"),
+    };
+    assert_eq!(
+        missing(client.set_dim("relay", "brightness", 50.0).await),
+        DeviceCapability::Dimmable
+    );
+    assert_eq!(
+        missing(
+            client
+                .set_color("relay", DeviceColor { r: 0, g: 0, b: 0 })
+                .await
+        ),
+        DeviceCapability::Color
+    );
+    assert_eq!(
+        missing(client.read_sensors("relay").await),
+        DeviceCapability::Sensor
+    );
+    assert!(!sent_action(&server));
+}
+
+#[tokio::test]
+async fn set_color_and_read_sensors() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_color_device("strip", "office", DeviceColor { r: 0, g: 0, b: 0 })
+            .with_sensor_device(
+                "thermometer",
+                "office",
+                vec![DeviceSensor {
+                    label: "temperature".to_string(),
+                    value: json!(21.5),
+                    hms_type: "float".to_string(),
+                    unit: "°C".to_string(),
+                }],
+            ),
+    )
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let orange = DeviceColor {
+        r: 255,
+        g: 128,
+        b: 0,
+    };
+    let device = client.set_color("strip", orange).await.unwrap();
+    assert_eq!(device.extractions.color, Some(orange));
+
+    let device = client.read_sensors("thermometer").await.unwrap();
+    assert_eq!(device.extractions.sensors.unwrap()[0].value, json!(21.5));
+}
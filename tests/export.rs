@@ -0,0 +1,97 @@
+use serde_json::{json, Value};
+use smarthome_sdk_rs::SystemExport;
+
+/// An export containing fields which are not modeled by the SDK on every level
+fn sample_export() -> Value {
+    json!({
+        "users": [{
+            "data": {
+                "username": "admin",
+                "password": "$2a$10$hash",
+                "forename": "Admin",
+                "surname": "User",
+                "primaryColorDark": "#88ff70",
+                "primaryColorLight": "#2e7d32",
+                "darkTheme": true,
+                "schedulerEnabled": true,
+                "profilePicture": "aGVsbG8="
+            },
+            "permissions": ["*"],
+            "devicePermissions": ["lamp"],
+            "cameraPermissions": [],
+            "homescripts": [{
+                "id": "greet",
+                "name": "Greet",
+                "description": "",
+                "quickActionsEnabled": false,
+                "schedulerEnabled": false,
+                "isWidget": false,
+                "code": "println('hi')",
+                "mdIcon": "code",
+                "type": "NORMAL",
+                "workspace": "default",
+                "owner": "admin"
+            }],
+            "automations": [{
+                "id": 1,
+                "name": "Morning",
+                "description": "",
+                "homescriptId": "greet",
+                "enabled": true,
+                "trigger": "cron"
+            }],
+            "schedules": [{
+                "id": 2,
+                "name": "Evening",
+                "hour": 20,
+                "minute": 15,
+                "targetMode": "code"
+            }],
+            "reminders": []
+        }],
+        "rooms": [{
+            "data": {
+                "id": "living",
+                "name": "Living Room",
+                "description": "",
+                "floor": 1
+            },
+            "cameras": [{
+                "id": "door",
+                "name": "Door",
+                "url": "http://camera",
+                "roomId": "living",
+                "rotation": 90
+            }],
+            "layout": { "x": 0, "y": 0 }
+        }],
+        "devices": [{
+            "type": "OUTPUT",
+            "id": "lamp",
+            "name": "Lamp",
+            "roomId": "living",
+            "vendorId": "mock",
+            "modelId": "mock",
+            "singletonJson": { "pin": 4 },
+            "powerState": true
+        }],
+        "hardwareNodes": [{
+            "url": "http://node",
+            "name": "Node",
+            "token": "secret",
+            "enabled": true,
+            "online": false
+        }],
+        "configuration": { "lockDownMode": false }
+    })
+}
+
+#[test]
+fn export_round_trip_preserves_unknown_fields() {
+    let sample = sample_export();
+    let export: SystemExport = serde_json::from_value(sample.clone()).unwrap();
+    assert_eq!(export.devices[0].value.id, "lamp");
+    assert_eq!(export.rooms[0].cameras[0].value.room_id, "living");
+
+    assert_eq!(serde_json::to_value(&export).unwrap(), sample);
+}
@@ -0,0 +1,353 @@
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use hyper::header::{CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode, Version};
+use reqwest::Certificate;
+use serde_json::json;
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{
+    Auth, Client, Error, HmsRunMode, HmsRunTarget, HomescriptData, HomescriptRunEvent,
+    HomescriptType,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::{
+    self, error::ProtocolError, handshake::derive_accept_key, protocol::Role, Message,
+};
+use tokio_tungstenite::WebSocketStream;
+
+fn homescript(id: &str) -> HomescriptData {
+    HomescriptData {
+        id: id.to_string(),
+        name: "Greeting".to_string(),
+        description: String::new(),
+        quick_actions_enabled: false,
+        scheduler_enabled: false,
+        is_widget: false,
+        code: "println('hello')".to_string(),
+        md_icon: "code".to_string(),
+        type_: HomescriptType::Normal,
+        workspace: "default".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn homescript_crud() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client
+        .create_homescript(&homescript("greet"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        client.create_homescript(&homescript("greet")).await,
+        Err(Error::Conflict(_))
+    ));
+
+    let mut modified = homescript("greet");
+    modified.code = "println('bye')".to_string();
+    client.modify_homescript(&modified).await.unwrap();
+    let homescripts = client.list_personal_homescripts().await.unwrap();
+    assert_eq!(homescripts[0].data.code, "println('bye')");
+
+    client.delete_homescript("greet").await.unwrap();
+    assert!(matches!(
+        client.delete_homescript("greet").await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn failed_runs_return_their_result() {
+    let mut state = MockState::default();
+    state.homescript_result.success = false;
+    state.homescript_result.output = "partial".to_string();
+    let server = MockServer::start(state).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let result = client
+        .exec_homescript_code("panic()", vec![], HmsRunMode::Execute)
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert_eq!(result.output, "partial");
+}
+
+#[tokio::test]
+async fn streaming_run() {
+    let mut state = MockState::default();
+    state.homescript_result.output = "hello\n".to_string();
+    let server = MockServer::start(state).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let mut run = client
+        .run_homescript_streaming(HmsRunTarget::Code("println('hello')"), vec![])
+        .await
+        .unwrap();
+    match run.next().await {
+        Some(Ok(HomescriptRunEvent::Output(output))) => assert_eq!(output, "hello\n"),
+        other => panic!("expected output, got {other:?}"),
+    }
+
+    let result = run.finish().await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.exit_code, Some(0));
+    assert_eq!(result.output, "hello\n");
+}
+
+async fn streaming_server(messages: Vec<serde_json::Value>) -> MockServer {
+    MockServer::start(MockState {
+        homescript_messages: Some(messages),
+        ..Default::default()
+    })
+    .await
+}
+
+fn exit_message(exit_code: i64) -> serde_json::Value {
+    json!({ "kind": "exit", "success": exit_code == 0, "exitCode": exit_code })
+}
+
+#[tokio::test]
+async fn stream_ends_at_the_exit_message() {
+    let server = streaming_server(vec![
+        json!({ "kind": "out", "payload": "a" }),
+        exit_message(3),
+        // Anything after the exit message is not part of the run
+        json!({ "kind": "out", "payload": "b" }),
+    ])
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let mut run = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .unwrap();
+    assert!(matches!(
+        run.next().await,
+        Some(Ok(HomescriptRunEvent::Output(_)))
+    ));
+    assert!(run.next().await.is_none());
+    assert!(run.next().await.is_none());
+
+    let result = run.finish().await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.exit_code, Some(3));
+    assert_eq!(result.output, "a");
+}
+
+#[tokio::test]
+async fn finish_collects_unconsumed_events() {
+    let server = streaming_server(vec![
+        json!({ "kind": "out", "payload": "a" }),
+        json!({ "kind": "out", "payload": "b" }),
+        exit_message(0),
+    ])
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let mut run = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .unwrap();
+    assert!(run.next().await.is_some());
+
+    let result = run.finish().await.unwrap();
+    assert_eq!(result.output, "ab");
+    assert_eq!(result.exit_code, Some(0));
+}
+
+#[tokio::test]
+async fn close_before_exit_is_an_error() {
+    let server = streaming_server(vec![json!({ "kind": "out", "payload": "a" })]).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let mut run = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .unwrap();
+    assert!(matches!(
+        run.next().await,
+        Some(Ok(HomescriptRunEvent::Output(_)))
+    ));
+    assert!(matches!(run.next().await, Some(Err(Error::WebSocket(_)))));
+    assert!(run.next().await.is_none());
+
+    let server = streaming_server(vec![json!({ "kind": "out", "payload": "a" })]).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+    let run = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .unwrap();
+    assert!(matches!(run.finish().await, Err(Error::WebSocket(_))));
+}
+
+/// Answers the version check and accepts every other request as a WebSocket upgrade,
+/// without proving that it understood the handshake
+async fn careless_upgrade_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                loop {
+                    let Ok(read @ 1..) = stream.read(&mut buffer).await else {
+                        return;
+                    };
+                    request.extend_from_slice(&buffer[..read]);
+                    if !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        continue;
+                    }
+
+                    let response = match request.starts_with(b"GET /api/version ") {
+                        true => {
+                            let body = r#"{"version":"0.10.0","goVersion":"go1.21"}"#;
+                            format!(
+                                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                                body.len()
+                            )
+                        }
+                        false => "HTTP/1.1 101 Switching Protocols\r\nconnection: upgrade\r\nupgrade: websocket\r\nsec-websocket-accept: bm90IHRoZSBleHBlY3RlZCBrZXk=\r\n\r\n".to_string(),
+                    };
+                    request.clear();
+                    if stream.write_all(response.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn mismatching_accept_key_is_rejected() {
+    let url = careless_upgrade_server().await;
+    let client = Client::new(&url, Auth::None, true).await.unwrap();
+
+    let err = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .err()
+        .unwrap();
+    let Error::WebSocket(err) = err else {
+        panic!("expected `WebSocket`, got {err:?}");
+    };
+    assert!(matches!(
+        *err,
+        tungstenite::Error::Protocol(ProtocolError::SecWebSocketAcceptKeyMismatch)
+    ));
+}
+
+/// Serves the version check and the WebSocket endpoint over TLS, offering both HTTP/2 and HTTP/1.1
+/// Like a typical reverse proxy, it only accepts upgrades on HTTP/1.1 connections
+/// Returns the URL and the certificate, along with the versions of every request
+async fn tls_upgrade_server() -> (String, Certificate, Arc<Mutex<Vec<Version>>>) {
+    let certificate = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let mut config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![rustls::Certificate(certificate.serialize_der().unwrap())],
+            rustls::PrivateKey(certificate.serialize_private_key_der()),
+        )
+        .unwrap();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "https://localhost:{}",
+        listener.local_addr().unwrap().port()
+    );
+    let versions = Arc::new(Mutex::new(vec![]));
+
+    let seen = versions.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            let seen = seen.clone();
+            tokio::spawn(async move {
+                let stream = acceptor.accept(stream).await.unwrap();
+                let service = service_fn(move |mut request: Request<Body>| {
+                    seen.lock().unwrap().push(request.version());
+                    async move { Ok::<_, Infallible>(tls_response(&mut request)) }
+                });
+                Http::new()
+                    .serve_connection(stream, service)
+                    .with_upgrades()
+                    .await
+                    .ok();
+            });
+        }
+    });
+
+    let certificate =
+        Certificate::from_pem(certificate.serialize_pem().unwrap().as_bytes()).unwrap();
+    (url, certificate, versions)
+}
+
+fn tls_response(request: &mut Request<Body>) -> Response<Body> {
+    if request.uri().path() == "/api/version" {
+        return Response::new(Body::from(r#"{"version":"0.10.0","goVersion":"go1.21"}"#));
+    }
+
+    let key = request.headers().get(SEC_WEBSOCKET_KEY).cloned();
+    let (Version::HTTP_11, Some(key)) = (request.version(), key) else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::empty())
+            .unwrap();
+    };
+
+    let upgrade = hyper::upgrade::on(request);
+    tokio::spawn(async move {
+        let mut socket =
+            WebSocketStream::from_raw_socket(upgrade.await.unwrap(), Role::Server, None).await;
+        // Waits for the client's initial message before announcing the end of the run
+        socket.next().await;
+        socket
+            .send(Message::Text(exit_message(0).to_string()))
+            .await
+            .ok();
+        socket.close(None).await.ok();
+    });
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(CONNECTION, "upgrade")
+        .header(UPGRADE, "websocket")
+        .header(SEC_WEBSOCKET_ACCEPT, derive_accept_key(key.as_bytes()))
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn upgrade_uses_http1_when_the_server_prefers_http2() {
+    let (url, certificate, versions) = tls_upgrade_server().await;
+    let client = Client::builder(&url)
+        .pin_certificate(certificate)
+        .build()
+        .await
+        .unwrap();
+
+    let run = client
+        .run_homescript_streaming(HmsRunTarget::Id("greet"), vec![])
+        .await
+        .unwrap();
+    assert!(run.finish().await.unwrap().success);
+
+    // Regular requests still use HTTP/2, only the upgrade falls back to HTTP/1.1
+    assert_eq!(
+        *versions.lock().unwrap(),
+        [Version::HTTP_2, Version::HTTP_11]
+    );
+}
@@ -0,0 +1,99 @@
+use futures_util::{StreamExt, TryStreamExt};
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Auth, Client, LogEvent, LogFilter, LogLevel};
+
+fn event(id: u64, level: LogLevel) -> LogEvent {
+    LogEvent {
+        id,
+        level,
+        name: format!("event {id}"),
+        description: String::new(),
+        time: id * 1000,
+    }
+}
+
+async fn server_with_logs(count: u64) -> MockServer {
+    MockServer::start(MockState {
+        logs: (1..=count)
+            .map(|id| match id % 2 {
+                0 => event(id, LogLevel::Error),
+                _ => event(id, LogLevel::Info),
+            })
+            .collect(),
+        ..Default::default()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn pager_streams_every_event_across_pages() {
+    let server = server_with_logs(7).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let events: Vec<LogEvent> = client
+        .log_pages(LogFilter::default(), 3)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        events.iter().map(|event| event.id).collect::<Vec<_>>(),
+        (1..=7).collect::<Vec<_>>()
+    );
+
+    // Two full pages and a partial one, the partial page ends the stream
+    let offsets: Vec<String> = server
+        .state()
+        .requests
+        .iter()
+        .filter(|request| request.path == "/api/logs/list/all")
+        .map(|request| request.query.clone().unwrap())
+        .collect();
+    assert_eq!(
+        offsets,
+        ["limit=3&offset=0", "limit=3&offset=3", "limit=3&offset=6"]
+    );
+}
+
+#[tokio::test]
+async fn pager_applies_the_filter() {
+    let server = server_with_logs(6).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    let mut pager = client.log_pages(
+        LogFilter {
+            min_level: Some(LogLevel::Error),
+            ..Default::default()
+        },
+        2,
+    );
+    assert_eq!(pager.next().await.unwrap().unwrap().id, 2);
+    // The remaining event of the buffered page is returned before the next page is fetched
+    assert_eq!(
+        pager
+            .next_page()
+            .await
+            .unwrap()
+            .unwrap()
+            .iter()
+            .map(|event| event.id)
+            .collect::<Vec<_>>(),
+        [4]
+    );
+    assert_eq!(pager.next().await.unwrap().unwrap().id, 6);
+    assert!(pager.next().await.is_none());
+}
+
+#[tokio::test]
+async fn empty_filter_omits_the_query() {
+    let server = server_with_logs(2).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    assert_eq!(
+        client.list_logs(&LogFilter::default()).await.unwrap().len(),
+        2
+    );
+    let state = server.state();
+    let request = state.requests.last().unwrap();
+    assert_eq!(request.path, "/api/logs/list/all");
+    assert_eq!(request.query, None);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{count, session_auth};
+use reqwest::StatusCode;
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Auth, Client, Error, RetryPolicy};
+
+fn fast_retries() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(1),
+        jitter: false,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn version_check_is_retried() {
+    let server = MockServer::start(
+        MockState::default().with_failure("/api/version", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+
+    Client::builder(server.url())
+        .retry_policy(fast_retries())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(count(&server, "/api/version"), 2);
+}
+
+#[tokio::test]
+async fn clients_retry_by_default() {
+    let server = MockServer::start(
+        MockState::default().with_failure("/api/version", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+
+    Client::new(server.url(), Auth::None, true).await.unwrap();
+    assert_eq!(count(&server, "/api/version"), 2);
+}
+
+#[tokio::test]
+async fn version_check_fails_without_retries() {
+    let server = MockServer::start(
+        MockState::default().with_failure("/api/version", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+
+    let err = Client::builder(server.url())
+        .retry_policy(RetryPolicy::disabled())
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.response().unwrap().status,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn login_is_retried_although_it_uses_post() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("admin", "admin")
+            .with_failure("/api/login", StatusCode::BAD_GATEWAY)
+            .with_failure("/api/login", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+
+    Client::builder(server.url())
+        .auth(session_auth("admin"))
+        .retry_policy(fast_retries())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(count(&server, "/api/login"), 3);
+}
+
+#[tokio::test]
+async fn session_relogin_is_retried() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("admin", "admin")
+            .with_room("hall", "Hall"),
+    )
+    .await;
+    let client = Client::builder(server.url())
+        .auth(session_auth("admin"))
+        .retry_policy(fast_retries())
+        .build()
+        .await
+        .unwrap();
+
+    {
+        let mut state = server.state();
+        state.sessions.clear();
+        state.failures.insert(
+            "/api/login".to_string(),
+            [StatusCode::SERVICE_UNAVAILABLE].into(),
+        );
+    }
+    assert_eq!(client.list_all_rooms().await.unwrap().len(), 1);
+    assert_eq!(count(&server, "/api/login"), 3);
+}
+
+#[tokio::test]
+async fn retries_stop_after_max_attempts() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_failure("/api/room/list/all", StatusCode::SERVICE_UNAVAILABLE)
+            .with_failure("/api/room/list/all", StatusCode::SERVICE_UNAVAILABLE)
+            .with_failure("/api/room/list/all", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+    let client = Client::builder(server.url())
+        .retry_policy(fast_retries())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.list_all_rooms().await,
+        Err(Error::Smarthome(_))
+    ));
+    assert_eq!(count(&server, "/api/room/list/all"), 3);
+}
+
+#[tokio::test]
+async fn post_requests_are_not_retried() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_device("lamp", "hall", false)
+            .with_failure("/api/devices/action/power", StatusCode::SERVICE_UNAVAILABLE),
+    )
+    .await;
+    let client = Client::builder(server.url())
+        .retry_policy(fast_retries())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(client.set_power("lamp", true).await.is_err());
+    assert_eq!(count(&server, "/api/devices/action/power"), 1);
+}
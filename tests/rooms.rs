@@ -0,0 +1,105 @@
+use bytes::Bytes;
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Auth, Camera, Client, Error, RoomData};
+
+fn room(id: &str, name: &str) -> RoomData {
+    RoomData {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: String::new(),
+    }
+}
+
+fn camera(id: &str, room_id: &str) -> Camera {
+    Camera {
+        id: id.to_string(),
+        name: "Front Door".to_string(),
+        url: "http://camera.local/feed".to_string(),
+        room_id: room_id.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn room_crud() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.create_room(&room("hall", "Hall")).await.unwrap();
+    client
+        .modify_room(&room("hall", "Entrance Hall"))
+        .await
+        .unwrap();
+    let rooms = client.list_all_rooms().await.unwrap();
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].data.name, "Entrance Hall");
+
+    client.delete_room("hall").await.unwrap();
+    assert!(client.list_all_rooms().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn rooms_contain_their_devices_and_cameras() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_room("hall", "Hall")
+            .with_room("office", "Office")
+            .with_device("lamp", "hall", true),
+    )
+    .await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.add_camera(&camera("door", "hall")).await.unwrap();
+    let rooms = client.personal_rooms().await.unwrap();
+    let hall = rooms.iter().find(|room| room.data.id == "hall").unwrap();
+    assert_eq!(hall.switches[0].shallow.id, "lamp");
+    assert_eq!(hall.cameras[0].id, "door");
+    let office = rooms.iter().find(|room| room.data.id == "office").unwrap();
+    assert!(office.switches.is_empty() && office.cameras.is_empty());
+}
+
+#[tokio::test]
+async fn camera_crud() {
+    let server = MockServer::start(MockState::default().with_room("hall", "Hall")).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.add_camera(&camera("door", "hall")).await.unwrap();
+    let mut modified = camera("door", "hall");
+    modified.name = "Back Door".to_string();
+    client.modify_camera(&modified).await.unwrap();
+    assert_eq!(server.state().cameras[0].name, "Back Door");
+
+    server
+        .state()
+        .camera_feeds
+        .insert("door".to_string(), Bytes::from_static(b"jpeg"));
+    assert_eq!(
+        client.camera_feed("door").await.unwrap(),
+        Bytes::from_static(b"jpeg")
+    );
+
+    client.delete_camera("door").await.unwrap();
+    assert!(server.state().cameras.is_empty());
+}
+
+#[tokio::test]
+async fn room_errors() {
+    let server = MockServer::start(MockState::default().with_room("hall", "Hall")).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    assert!(matches!(
+        client.create_room(&room("hall", "Hall")).await,
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        client.modify_room(&room("attic", "Attic")).await,
+        Err(Error::NotFound(_))
+    ));
+    assert!(matches!(
+        client.delete_camera("door").await,
+        Err(Error::NotFound(_))
+    ));
+    assert!(matches!(
+        client.camera_feed("door").await,
+        Err(Error::NotFound(_))
+    ));
+}
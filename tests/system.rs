@@ -0,0 +1,170 @@
+use serde_json::json;
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{
+    Auth, Client, CreateDriverRequest, Error, ExportRequest, HomescriptExecError, Permission, User,
+};
+
+fn driver_request() -> CreateDriverRequest {
+    CreateDriverRequest {
+        vendor_id: "acme".to_string(),
+        model_id: "relay".to_string(),
+        name: "Relay".to_string(),
+        version: "1.0.0".to_string(),
+        homescript_code: "fn set_power() {}".to_string(),
+    }
+}
+
+fn validation_error() -> HomescriptExecError {
+    serde_json::from_value(json!({
+        "syntaxError": null,
+        "diagnosticError": { "kind": 2, "message": "`host` must be a string", "notes": [] },
+        "runtimeError": null,
+        "span": {
+            "start": { "line": 1, "column": 1, "index": 0 },
+            "end": { "line": 1, "column": 5, "index": 4 },
+            "filename": "acme:relay"
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn driver_management() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+
+    client.create_driver(&driver_request()).await.unwrap();
+    assert!(matches!(
+        client.create_driver(&driver_request()).await,
+        Err(Error::Conflict(_))
+    ));
+
+    client
+        .modify_driver_code("acme", "relay", "fn set_power(on: bool) {}")
+        .await
+        .unwrap();
+    client
+        .set_driver_configuration("acme", "relay", json!({ "host": "10.0.0.2" }))
+        .await
+        .unwrap();
+    let drivers = client.list_drivers().await.unwrap();
+    assert_eq!(
+        drivers[0].driver.homescript_code,
+        "fn set_power(on: bool) {}"
+    );
+    assert_eq!(drivers[0].configuration, json!({ "host": "10.0.0.2" }));
+
+    client.delete_driver("acme", "relay").await.unwrap();
+    assert!(matches!(
+        client.delete_driver("acme", "relay").await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn driver_validation() {
+    let server = MockServer::start(MockState::default()).await;
+    let client = Client::new(server.url(), Auth::None, true).await.unwrap();
+    client.create_driver(&driver_request()).await.unwrap();
+
+    client
+        .validate_driver_configuration("acme", "relay", json!({ "host": "10.0.0.2" }))
+        .await
+        .unwrap();
+
+    {
+        let mut state = server.state();
+        state.drivers[0].is_valid = false;
+        state.drivers[0].validation_errors = vec![validation_error()];
+    }
+    match client
+        .validate_driver_configuration("acme", "relay", json!({ "host": 42 }))
+        .await
+    {
+        Err(Error::DriverValidation(errors)) => assert_eq!(
+            errors[0].diagnostic_error.as_ref().unwrap().message,
+            "`host` must be a string"
+        ),
+        other => panic!("expected `DriverValidation`, got {other:?}"),
+    }
+
+    assert!(matches!(
+        client
+            .validate_driver_configuration("acme", "dimmer", json!({}))
+            .await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn export_and_import() {
+    let exported = json!({
+        "users": [],
+        "rooms": [{
+            "data": { "id": "hall", "name": "Hall", "description": "" },
+            "cameras": []
+        }],
+        "devices": [],
+        "hardwareNodes": [],
+        "configuration": { "lockDownMode": false }
+    });
+    let production = MockServer::start(MockState {
+        export: exported.clone(),
+        ..Default::default()
+    })
+    .await;
+    let staging = MockServer::start(MockState::default()).await;
+
+    let production_client = Client::new(production.url(), Auth::None, true)
+        .await
+        .unwrap();
+    let staging_client = Client::new(staging.url(), Auth::None, true).await.unwrap();
+
+    let export = production_client
+        .export_system(&ExportRequest {
+            include_profile_pictures: false,
+            include_cache_data: false,
+        })
+        .await
+        .unwrap();
+    assert_eq!(export.rooms[0].data.value.id, "hall");
+
+    staging_client.import_config(&export).await.unwrap();
+    assert_eq!(staging.state().export, exported);
+}
+
+#[tokio::test]
+async fn personal_permissions() {
+    let server = MockServer::start(
+        MockState::default()
+            .with_user("alice", "secret")
+            .with_permission("alice", Permission::Other("setPower".to_string()))
+            .with_user("bob", "secret")
+            .with_permission("bob", Permission::Wildcard),
+    )
+    .await;
+    let login = |username: &str| {
+        Client::new(
+            server.url(),
+            Auth::QueryPassword(User {
+                username: username.to_string(),
+                password: "secret".to_string(),
+            }),
+            true,
+        )
+    };
+
+    let alice = login("alice").await.unwrap();
+    assert_eq!(
+        alice.personal_permissions().await.unwrap(),
+        [Permission::SetPower]
+    );
+    assert!(alice.has_permission(&Permission::SetPower).await.unwrap());
+    assert!(matches!(
+        alice.require_permission(Permission::Logs).await,
+        Err(Error::MissingPermission(Permission::Logs))
+    ));
+
+    let bob = login("bob").await.unwrap();
+    assert!(bob.has_permission(&Permission::Logs).await.unwrap());
+}
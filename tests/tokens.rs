@@ -0,0 +1,22 @@
+use smarthome_sdk_rs::mock::{MockServer, MockState};
+use smarthome_sdk_rs::{Auth, Client};
+
+#[tokio::test]
+async fn token_login_reports_user_and_label() {
+    let server = MockServer::start(MockState::default().with_user("admin", "admin").with_token(
+        "admin",
+        "secret-token",
+        "ci",
+    ))
+    .await;
+
+    let client = Client::new(
+        server.url(),
+        Auth::QueryToken("secret-token".to_string()),
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(client.username.as_deref(), Some("admin"));
+    assert_eq!(client.token_label.as_deref(), Some("ci"));
+}